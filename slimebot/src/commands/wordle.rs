@@ -1,12 +1,36 @@
-use poise::serenity_prelude::{Mentionable, User};
-use std::ops::Not;
+use poise::serenity_prelude::{CreateAttachment, CreateMessage, User, UserId};
+use poise::CreateReply;
+use std::{collections::HashMap, ops::Not};
 use tracing::{debug, instrument};
 
 use crate::utils::poise::{CommandResult, Context, ContextExt};
-use crate::{errors::SendMessageError, Result};
+use crate::{
+    errors::{CommandError, SendMessageError},
+    Result,
+};
 
 pub mod core;
-use core::{self as wordle, game::options::GameOptionsBuilder, game::options::GameStyle, AsEmoji};
+use core::{
+    self as wordle,
+    game::options::{BoardCount, GameOptions, GameOptionsBuilder, GameStyle},
+    AsEmoji, HasLength, HintKind, Language, LeaderboardPeriod, WordLength, WordleData,
+};
+
+/// Calls a function that's generic over word length, with a length only known at runtime.
+macro_rules! with_length {
+    ($ctx:expr, $length:expr, $function:ident($($arg:expr),* $(,)?)) => {
+        match $length {
+            4 => $function::<4>($($arg),*).await?,
+            5 => $function::<5>($($arg),*).await?,
+            6 => $function::<6>($($arg),*).await?,
+            7 => $function::<7>($($arg),*).await?,
+            _ => {
+                $ctx.reply_ephemeral("words have to be between 4 and 7 letters long!")
+                    .await?;
+            }
+        }
+    };
+}
 
 /// play wordle right from discord!
 #[instrument(skip_all)]
@@ -15,7 +39,25 @@ use core::{self as wordle, game::options::GameOptionsBuilder, game::options::Gam
     prefix_command,
     discard_spare_arguments,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL",
-    subcommands("daily", "random", "display", "role", "unused")
+    subcommands(
+        "daily",
+        "random",
+        "archive",
+        "display",
+        "stats",
+        "leaderboard",
+        "role",
+        "unused",
+        "hint",
+        "race",
+        "multi",
+        "absurdle",
+        "guess",
+        "challenge",
+        "accept",
+        "speedrun",
+        "fastest"
+    )
 )]
 pub async fn wordle(ctx: Context<'_>) -> Result<()> {
     _wordle(ctx).await?;
@@ -47,82 +89,99 @@ async fn _wordle(ctx: Context<'_>) -> CommandResult {
     discard_spare_arguments,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn daily(ctx: Context<'_>, style: Option<GameStyle>) -> Result<()> {
-    _daily(ctx, style).await?;
+async fn daily(
+    ctx: Context<'_>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+    #[description = "play the daily for another language"] language: Option<Language>,
+    #[description = "let the server watch your board's colors as you play"] spectate: Option<bool>,
+) -> Result<()> {
+    _daily(
+        ctx,
+        style,
+        hard_mode,
+        language,
+        spectate.unwrap_or_default(),
+    )
+    .await?;
     Ok(())
 }
 
-async fn _daily(ctx: Context<'_>, style: Option<GameStyle>) -> CommandResult {
+async fn _daily(
+    ctx: Context<'_>,
+    style: Option<GameStyle>,
+    hard_mode: Option<bool>,
+    language: Option<Language>,
+    spectate: bool,
+) -> CommandResult {
     let wordle = ctx.data().wordle();
-    let wordles = wordle.wordles();
-
-    if let Some(new_daily) = wordles.refresh(wordle.words()).await?
-        && let Some(channel) = ctx.data().config().wordle.channel_id
-        && let Some(role) = ctx.data().config().wordle.role_id
-    {
-        channel
-            .say(
-                ctx,
-                format!(
-                    "{ping} **Daily wordle {number} now available!**\nPlay it with `/wordle daily`",
-                    ping = role.mention(),
-                    number = new_daily.puzzle.number
-                ),
-            )
-            .await?;
-    }
+
+    let language = match language.filter(|language| !language.is_english()) {
+        Some(language) => {
+            let Some(data) = wordle.language(language) else {
+                ctx.reply_ephemeral(format!("there's no {} words list set up!", language.name()))
+                    .await?;
+
+                return Ok(());
+            };
+
+            Some(data)
+        }
+        None => None,
+    };
+
+    let wordles = language.map_or(
+        wordle.wordles_in(ctx.guild_id()),
+        wordle::LanguageData::wordles,
+    );
 
     let mut playable = wordles.playable_for(ctx.author().id).await?;
 
     if let Some(daily) = playable.next() {
-        if let Some(data) = wordle.game_data().get(ctx.channel_id()).await {
-            ctx.reply_ephemeral(format!(
-                "there's already a game being played in this channel! {}",
-                data.message_id.link(ctx.channel_id(), ctx.guild_id()),
-            ))
-            .await?;
-
+        if channel_busy(ctx).await? {
             return Ok(());
-        } else {
-            // play game
-            let mut game = wordle::Game::new(
-                ctx,
-                daily.puzzle.clone(),
-                GameOptionsBuilder::default().style(style).build(),
-            )
-            .await?;
+        }
 
-            game.setup().await?;
-            game.run().await?;
+        // a paused game picks up where it was left off
+        let paused = daily
+            .user_game(ctx.author().id)
+            .filter(|game| game.in_progress());
 
-            if let Some(completed) = wordle
-                .wordles()
-                .find_game(ctx.author().id, daily.puzzle.number)
-                .await?
-                && let Some(channel) = &ctx.data().config().wordle.channel_id
-            {
-                channel
-                    .say(
-                        ctx,
-                        format!(
-                            "`{username}` **completed wordle {number}!**\n{emojis}",
-                            username = ctx.author().name,
-                            number = daily.puzzle.number,
-                            emojis = completed.as_emoji()
-                        ),
-                    )
-                    .await?;
-            }
+        // play game
+        let mut game = wordle::Game::new(
+            ctx,
+            daily.puzzle.clone(),
+            GameOptionsBuilder::default()
+                .style(style)
+                .hard_mode(hard_mode.or(paused.map(|game| game.hard_mode)))
+                .build(),
+        )
+        .await?;
+
+        if let Some(language) = language {
+            game = game.in_language(language);
         }
-    } else {
-        let latest = wordles
-            .latest()
-            .await?
-            .expect("wordle has been refreshed by now");
 
+        if let Some(paused) = paused {
+            game.resume(paused);
+        }
+
+        game.setup().await?;
+
+        // outside of a server, there's nowhere for anyone to watch from
+        if spectate
+            && ctx.guild_id().is_some()
+            && let Some(channel) = ctx.data().config().wordle.channel_in(wordles.guild())
+        {
+            game.spectate(channel).await?;
+        }
+
+        game.play_out().await?;
+    } else {
         ctx.reply_ephemeral(format!(
             "you don't have a daily wordle yet! check back in {hours} hours",
-            hours = latest.age_hours()
+            hours = wordles.hours_until_rollover()
         ))
         .await?;
     }
@@ -138,35 +197,195 @@ async fn _daily(ctx: Context<'_>, style: Option<GameStyle>) -> CommandResult {
     discard_spare_arguments,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn random(ctx: Context<'_>, style: Option<GameStyle>) -> Result<()> {
+async fn random(
+    ctx: Context<'_>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+    #[description = "how many letters the word has (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+    #[description = "play the word from a share code"] code: Option<String>,
+    #[description = "play in another language (5 letters only)"] language: Option<Language>,
+) -> Result<()> {
     let result: CommandResult = try {
         let wordle = ctx.data().wordle();
 
         debug!(data = ?wordle.game_data());
 
-        if let Some(data) = wordle.game_data().get(ctx.channel_id()).await {
-            ctx.reply_ephemeral(format!(
-                "there's already a game being played in this channel! {}",
-                data.message_id.link(ctx.channel_id(), ctx.guild_id()),
-            ))
-            .await?;
+        if channel_busy(ctx).await? {
+            return Ok(());
+        }
+
+        let options = GameOptionsBuilder::default()
+            .style(style)
+            .hard_mode(hard_mode)
+            .build();
+
+        let answer = match code.as_deref().map(wordle::share_code::decode) {
+            Some(Some(answer)) => Some(answer),
+            Some(None) => {
+                ctx.reply_ephemeral("that isn't a valid code!").await?;
+                return Ok(());
+            }
+            None => None,
+        };
+
+        // a code already knows its length
+        let length = answer.as_ref().map(String::len).or(length).unwrap_or(5);
+
+        if let Some(language) = language.filter(|language| !language.is_english()) {
+            if length == 5 {
+                language_game(ctx, options, answer, language).await?;
+            } else {
+                ctx.reply_ephemeral("other languages only have 5-letter words!")
+                    .await?;
+            }
 
             return Ok(());
-        } else {
-            let puzzle = wordle::Puzzle::random(wordle.words());
+        }
 
-            let mut game = wordle::Game::new(
-                ctx,
-                puzzle,
-                GameOptionsBuilder::default().style(style).build(),
-            )
+        with_length!(ctx, length, random_game(ctx, options, answer));
+    };
+
+    result?;
+
+    Ok(())
+}
+
+async fn random_game<const N: usize>(
+    ctx: Context<'_>,
+    options: GameOptions,
+    answer: Option<String>,
+) -> CommandResult
+where
+    WordleData: HasLength<N>,
+{
+    let Some(length) = length_or_reply::<N>(ctx).await? else {
+        return Ok(());
+    };
+
+    let Some(puzzle) = random_puzzle(ctx, length.words(), answer).await? else {
+        return Ok(());
+    };
+
+    let mut game = wordle::Game::new(ctx, puzzle, options).await?;
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(())
+}
+
+async fn language_game(
+    ctx: Context<'_>,
+    options: GameOptions,
+    answer: Option<String>,
+    language: Language,
+) -> CommandResult {
+    let Some(data) = ctx.data().wordle().language(language) else {
+        ctx.reply_ephemeral(format!("there's no {} words list set up!", language.name()))
+            .await?;
+
+        return Ok(());
+    };
+
+    let Some(puzzle) = random_puzzle(ctx, data.words(), answer).await? else {
+        return Ok(());
+    };
+
+    let mut game = wordle::Game::new(ctx, puzzle, options)
+        .await?
+        .in_language(data);
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(())
+}
+
+/// Says so if there's already a game being played in the channel.
+async fn channel_busy(ctx: Context<'_>) -> std::result::Result<bool, CommandError> {
+    let Some(message) = ctx.data().wordle().active_game(ctx.channel_id()).await else {
+        return Ok(false);
+    };
+
+    ctx.reply_ephemeral(format!(
+        "there's already a game being played in this channel! {}",
+        message.link(ctx.channel_id(), ctx.guild_id()),
+    ))
+    .await?;
+
+    Ok(true)
+}
+
+/// The words list and games for `N`-letter words, or says there isn't a list for them.
+async fn length_or_reply<const N: usize>(
+    ctx: Context<'_>,
+) -> std::result::Result<Option<&WordLength<N>>, CommandError>
+where
+    WordleData: HasLength<N>,
+{
+    let length = HasLength::<N>::length(ctx.data().wordle());
+
+    if length.is_none() {
+        ctx.reply_ephemeral(format!("there's no {N}-letter words list set up!"))
             .await?;
+    }
+
+    Ok(length)
+}
+
+/// The word from a share code, or a random one. Gives nothing if the code's word isn't in the list.
+async fn random_puzzle<const N: usize>(
+    ctx: Context<'_>,
+    words: &kwordle::WordsList<N>,
+    answer: Option<String>,
+) -> std::result::Result<Option<wordle::Puzzle<N>>, CommandError> {
+    match answer {
+        Some(answer) => match kwordle::Word::from_str(words, &answer) {
+            Ok(answer) => Ok(Some(wordle::Puzzle::Random(answer))),
+            Err(_) => {
+                ctx.reply_ephemeral("that code isn't for a word in the words list!")
+                    .await?;
 
-            game.setup().await?;
+                Ok(None)
+            }
+        },
+        None => Ok(Some(wordle::Puzzle::random(words))),
+    }
+}
 
-            // play game
-            game.run().await?;
+/// race the clock on a random wordle
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn speedrun(
+    ctx: Context<'_>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+    #[description = "how many letters the word has (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        if channel_busy(ctx).await? {
+            return Ok(());
         }
+
+        let options = GameOptionsBuilder::default()
+            .style(style)
+            .hard_mode(hard_mode)
+            .build();
+
+        with_length!(ctx, length.unwrap_or(5), speedrun_game(ctx, options));
     };
 
     result?;
@@ -174,7 +393,24 @@ async fn random(ctx: Context<'_>, style: Option<GameStyle>) -> Result<()> {
     Ok(())
 }
 
-/// display your own results for a given wordle, or someone else's
+async fn speedrun_game<const N: usize>(ctx: Context<'_>, options: GameOptions) -> CommandResult
+where
+    WordleData: HasLength<N>,
+{
+    let Some(length) = length_or_reply::<N>(ctx).await? else {
+        return Ok(());
+    };
+
+    let puzzle = wordle::Puzzle::random(length.words());
+    let mut game = wordle::Game::new(ctx, puzzle, options).await?.timed();
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(())
+}
+
+/// practice with a past daily wordle
 #[instrument(skip_all)]
 #[poise::command(
     slash_command,
@@ -182,43 +418,64 @@ async fn random(ctx: Context<'_>, style: Option<GameStyle>) -> Result<()> {
     discard_spare_arguments,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn display(
+async fn archive(
     ctx: Context<'_>,
     #[description = "the wordle's number"] number: u32,
-    #[description = "the user to show results for (defaults to you)"] user: Option<User>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
 ) -> Result<()> {
     let result: CommandResult = try {
-        let _typing = ctx.defer_or_broadcast().await?;
-
-        let wordles = ctx.data().wordle.wordles();
+        let wordle = ctx.data().wordle();
+        let wordles = wordle.wordles_in(ctx.guild_id());
 
-        if wordles.wordle_exists(number).await?.not() {
+        let Some(daily) = wordles.find_daily(number).await? else {
             ctx.reply_ephemeral("that wordle doesn't exist!").await?;
             return Ok(());
+        };
+
+        if daily.is_expired(wordles.schedule()).not() {
+            ctx.reply_ephemeral(format!(
+                "wordle {number} isn't in the archive yet! play it with `/wordle daily`"
+            ))
+            .await?;
+            return Ok(());
         }
 
-        let user = user.as_ref().unwrap_or_else(|| ctx.author());
+        let archived = wordle
+            .archive()
+            .find_game(wordles.guild(), ctx.author().id, number)
+            .await?;
 
-        if let Some(game) = wordles.find_game(user.id, number).await? {
-            if game.num_guesses == 0 {
-                ctx.reply_ephemeral(
-                    "that user has started the wordle but hasn't guessed anything!",
-                )
+        if archived.as_ref().is_some_and(|game| game.is_finished()) {
+            ctx.reply_ephemeral(format!("you've already played archived wordle {number}!"))
                 .await?;
-            }
+            return Ok(());
+        }
 
-            let text = format!(
-                "wordle {} (`{}`):\n>>> {}",
-                number,
-                user.name,
-                game.as_emoji()
-            );
+        if channel_busy(ctx).await? {
+            return Ok(());
+        }
 
-            ctx.reply_ext(text).await?;
-        } else {
-            ctx.reply_ephemeral("that user hasn't started that wordle!")
-                .await?;
+        // an unfinished attempt picks up where it was left off
+        let paused = archived.filter(|game| game.in_progress());
+
+        let mut game = wordle::Game::new(
+            ctx,
+            wordle::Puzzle::Archive(daily.puzzle),
+            GameOptionsBuilder::default()
+                .style(style)
+                .hard_mode(hard_mode.or(paused.as_ref().map(|game| game.hard_mode)))
+                .build(),
+        )
+        .await?;
+
+        if let Some(paused) = &paused {
+            game.resume(paused);
         }
+
+        game.setup().await?;
+        game.play_out().await?;
     };
 
     result?;
@@ -232,25 +489,100 @@ async fn display(
     slash_command,
     prefix_command,
     discard_spare_arguments,
-    guild_only,
-    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL | MANAGE_ROLES"
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn role(ctx: Context<'_>) -> Result<()> {
+async fn display(
+    ctx: Context<'_>,
+    #[description = "the wordle's number"] number: u32,
+    #[description = "the user to show results for (defaults to you)"] user: Option<User>,
+    #[description = "draw the boards as pictures"] image: Option<bool>,
+    #[description = "show a challenge with this number instead"] challenge: Option<bool>,
+    #[description = "the language the wordle was in (defaults to english)"] language: Option<
+        Language,
+    >,
+) -> Result<()> {
     let result: CommandResult = try {
-        let config = ctx.data().config();
+        let _typing = ctx.defer_or_broadcast().await?;
 
-        if let Some(role_id) = &config.wordle.role_id {
-            let member = ctx.author_member().await.expect("command is guild-only");
-            if member.roles.contains(role_id) {
-                member.remove_role(ctx, role_id).await?;
-                ctx.reply_ephemeral("Removed the wordle role!").await?;
-            } else {
-                member.add_role(ctx, role_id).await?;
-                ctx.reply_ephemeral("Gave you the wordle role!").await?;
-            }
+        if challenge.unwrap_or_default() {
+            display_challenge(ctx, number, image.unwrap_or_default()).await?;
+            return Ok(());
+        }
+
+        let wordle = ctx.data().wordle();
+        let language = language.unwrap_or_default();
+
+        let wordles = if language.is_english() {
+            wordle.wordles_in(ctx.guild_id())
         } else {
-            ctx.reply_ephemeral("Error: there's no wordle role configured!")
+            let Some(data) = wordle.language(language) else {
+                ctx.reply_ephemeral(format!("there's no {} words list set up!", language.name()))
+                    .await?;
+                return Ok(());
+            };
+
+            data.wordles()
+        };
+
+        if wordles.wordle_exists(number).await?.not() {
+            ctx.reply_ephemeral("that wordle doesn't exist!").await?;
+            return Ok(());
+        }
+
+        let user = user.as_ref().unwrap_or_else(|| ctx.author());
+
+        let original = wordles.find_game(user.id, number).await?;
+        // only english dailies can be played from the archive
+        let archived = if language.is_english() {
+            wordle
+                .archive()
+                .find_game(wordles.guild(), user.id, number)
+                .await?
+        } else {
+            None
+        };
+
+        let image = image.unwrap_or_default();
+        let mut attachments = Vec::new();
+
+        let attempts: Vec<String> = [("daily", original), ("archive", archived)]
+            .into_iter()
+            .filter_map(|(kind, game)| {
+                let game = game?;
+
+                let emojis = if game.num_guesses == 0 {
+                    "no guesses yet!".into()
+                } else if image {
+                    let png = wordle::render::board(&game.guesses(None));
+                    attachments.push(CreateAttachment::bytes(png, format!("{kind}.png")));
+                    "".into()
+                } else {
+                    game.as_emoji()
+                };
+
+                Some(format!(
+                    "**{kind}**{hard_mode}\n{emojis}",
+                    hard_mode = if game.hard_mode { " (hard mode)" } else { "" },
+                ))
+            })
+            .collect();
+
+        if attempts.is_empty() {
+            ctx.reply_ephemeral("that user hasn't started that wordle!")
                 .await?;
+        } else {
+            let text = format!(
+                "wordle {number} (`{name}`):\n>>> {attempts}",
+                name = user.name,
+                attempts = attempts.join("\n\n")
+            );
+
+            let reply = attachments.into_iter().fold(
+                CreateReply::default().reply(true).content(text),
+                |reply, attachment| reply.attachment(attachment),
+            );
+
+            ctx.send_ext(reply).await?;
         }
     };
 
@@ -259,46 +591,295 @@ async fn role(ctx: Context<'_>) -> Result<()> {
     Ok(())
 }
 
-/* #[instrument(skip_all)]
+async fn display_challenge(ctx: Context<'_>, number: u32, image: bool) -> CommandResult {
+    let Some(challenge) = ctx.data().wordle().challenges().find(number).await? else {
+        ctx.reply_ephemeral("that challenge doesn't exist!").await?;
+        return Ok(());
+    };
+
+    let challenger = challenge.challenger.to_user(ctx).await?;
+    let target = challenge.target.to_user(ctx).await?;
+
+    let Some(game) = challenge.game.as_ref().filter(|game| game.num_guesses > 0) else {
+        ctx.reply_ephemeral(format!("`{}` hasn't started that challenge!", target.name))
+            .await?;
+        return Ok(());
+    };
+
+    // the word stays hidden until the challenge is over
+    let word = if challenge.is_finished() {
+        format!("\nthe word was: ||{}||", challenge.answer())
+    } else {
+        String::new()
+    };
+
+    let mut reply = CreateReply::default().reply(true);
+
+    let emojis = if image {
+        let png = wordle::render::board(&game.guesses(None));
+        reply = reply.attachment(CreateAttachment::bytes(png, "challenge.png"));
+        "".into()
+    } else {
+        game.as_emoji()
+    };
+
+    let text = format!(
+        "challenge {number} (`{challenger}` → `{target}`){hard_mode}:\n>>> {emojis}{word}",
+        challenger = challenger.name,
+        target = target.name,
+        hard_mode = if game.hard_mode { " (hard mode)" } else { "" },
+    );
+
+    ctx.send_ext(reply.content(text)).await?;
+
+    Ok(())
+}
+
+/// show daily wordle statistics for yourself or someone else
+#[instrument(skip_all)]
 #[poise::command(
     slash_command,
     prefix_command,
     discard_spare_arguments,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn letters(ctx: Context<'_>) -> Result<()> {
+async fn stats(
+    ctx: Context<'_>,
+    #[description = "the user to show statistics for (defaults to you)"] user: Option<User>,
+) -> Result<()> {
     let result: CommandResult = try {
-        let wordle = ctx.data().wordle();
+        let _typing = ctx.defer_or_broadcast().await?;
 
-        if let Some(data) = wordle.game_data().get(ctx.channel_id()).await {
-            let guesses = &data.guesses;
+        let wordles = ctx.data().wordle.wordles_in(ctx.guild_id());
+        let user = user.as_ref().unwrap_or_else(|| ctx.author());
 
-            let response = format!(
-                "guessed letters:\n{guessed}\n\nunused letters:\n{unused}",
-                guessed = guesses.letter_states().as_emoji(),
-                unused = guesses.unused_letters().as_emoji()
-            );
+        let stats = wordles.user_stats(user.id).await?;
 
-            ctx.reply(response).await?;
-        } else {
-            ctx.reply_ephemeral("there isn't a game active in this channel!")
+        if stats.is_empty() {
+            ctx.reply_ephemeral("that user hasn't finished any daily wordles!")
                 .await?;
+            return Ok(());
         }
+
+        let latest = wordles
+            .latest()
+            .await?
+            .map_or(0, |daily| daily.puzzle.number);
+
+        let text = format!(
+            "daily wordle stats (`{name}`):\n>>> **played:** {played}\n**win %:** {percentage}\n**current streak:** {current}\n**max streak:** {max}\n**hard mode solves:** {hard}\n**solves with hints:** {assisted}\n\n**guess distribution:**\n{distribution}",
+            name = user.name,
+            played = stats.played,
+            percentage = stats.win_percentage(),
+            current = stats.current_streak(latest),
+            max = stats.max_streak(),
+            hard = stats.hard_solved,
+            assisted = stats.assisted,
+            distribution = stats,
+        );
+
+        ctx.reply_ext(text).await?;
     };
 
     result?;
 
     Ok(())
-} */
+}
 
+/// rank this server's daily wordle players
 #[instrument(skip_all)]
 #[poise::command(
     slash_command,
     prefix_command,
     discard_spare_arguments,
+    guild_only,
     required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
 )]
-async fn unused(ctx: Context<'_>) -> Result<()> {
+async fn leaderboard(
+    ctx: Context<'_>,
+    #[description = "how far back to count (defaults to the last week)"] period: Option<
+        LeaderboardPeriod,
+    >,
+) -> Result<()> {
+    const SHOWN: usize = 10;
+
+    let result: CommandResult = try {
+        let _typing = ctx.defer_or_broadcast().await?;
+
+        let period = period.unwrap_or_default();
+        let guild = ctx.guild_id().expect("command is guild-only");
+        let members = member_names(ctx);
+        let users: Vec<UserId> = members.keys().copied().collect();
+
+        let rows: Vec<String> = ctx
+            .data()
+            .wordle
+            .wordles_in(Some(guild))
+            .leaderboard(period, &users)
+            .await?
+            .into_iter()
+            .filter_map(|entry| Some((members.get(&entry.user)?, entry)))
+            .take(SHOWN)
+            .enumerate()
+            .map(|(index, (name, entry))| format!(
+                "**{rank}.** `{name}`: {solved}/{played} solved, {average} avg guesses, {streak} max streak",
+                rank = index + 1,
+                solved = entry.solved,
+                played = entry.played,
+                average = entry
+                    .average_guesses()
+                    .map_or("-".to_owned(), |average| format!("{average:.2}")),
+                streak = entry.max_streak(),
+            ))
+            .collect();
+
+        if rows.is_empty() {
+            ctx.reply_ephemeral(format!(
+                "nobody here has finished a daily wordle {}!",
+                period.description()
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let text = format!(
+            "daily wordle leaderboard {period}:\n>>> {rows}",
+            period = period.description(),
+            rows = rows.join("\n")
+        );
+
+        ctx.reply_ext(text).await?;
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// The display names of everyone in the server, from the cache.
+fn member_names(ctx: Context<'_>) -> HashMap<UserId, String> {
+    ctx.guild()
+        .map(|guild| {
+            guild
+                .members
+                .iter()
+                .map(|(id, member)| (*id, member.display_name().to_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// rank this server's fastest wordle speedruns
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    guild_only,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn fastest(
+    ctx: Context<'_>,
+    #[description = "how many letters the words had (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+    #[description = "rank hard mode speedruns instead"] hard_mode: Option<bool>,
+) -> Result<()> {
+    const SHOWN: usize = 10;
+
+    let result: CommandResult = try {
+        let _typing = ctx.defer_or_broadcast().await?;
+
+        let length = length.unwrap_or(5);
+        let hard_mode = hard_mode.unwrap_or_default();
+        let guild = ctx.guild_id().expect("command is guild-only");
+        let members = member_names(ctx);
+        let users: Vec<UserId> = members.keys().copied().collect();
+
+        let rows: Vec<String> = ctx
+            .data()
+            .wordle
+            .speedruns()
+            .fastest(guild, length, hard_mode, &users, SHOWN as i64)
+            .await?
+            .into_iter()
+            .filter_map(|solve| Some((members.get(&solve.user)?, solve)))
+            .enumerate()
+            .map(|(index, (name, solve))| {
+                format!(
+                    "**{rank}.** `{name}`: {time} in {guesses} guesses",
+                    rank = index + 1,
+                    time = wordle::format_time(solve.millis),
+                    guesses = solve.guesses,
+                )
+            })
+            .collect();
+
+        let kind = if hard_mode { " hard mode" } else { "" };
+
+        if rows.is_empty() {
+            ctx.reply_ephemeral(format!(
+                "nobody here has finished a {length}-letter{kind} speedrun yet!"
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let text = format!(
+            "fastest {length}-letter{kind} wordle speedruns:\n>>> {rows}",
+            rows = rows.join("\n")
+        );
+
+        ctx.reply_ext(text).await?;
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// display your own results for a given wordle, or someone else's
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    guild_only,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL | MANAGE_ROLES"
+)]
+async fn role(ctx: Context<'_>) -> Result<()> {
+    let result: CommandResult = try {
+        let config = ctx.data().config();
+
+        if let Some(role_id) = &config.wordle.role_in(ctx.guild_id()) {
+            let member = ctx.author_member().await.expect("command is guild-only");
+            if member.roles.contains(role_id) {
+                member.remove_role(ctx, role_id).await?;
+                ctx.reply_ephemeral("Removed the wordle role!").await?;
+            } else {
+                member.add_role(ctx, role_id).await?;
+                ctx.reply_ephemeral("Gave you the wordle role!").await?;
+            }
+        } else {
+            ctx.reply_ephemeral("Error: there's no wordle role configured!")
+                .await?;
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/* #[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn letters(ctx: Context<'_>) -> Result<()> {
     let result: CommandResult = try {
         let wordle = ctx.data().wordle();
 
@@ -306,7 +887,8 @@ async fn unused(ctx: Context<'_>) -> Result<()> {
             let guesses = &data.guesses;
 
             let response = format!(
-                "unused letters:\n{unused}",
+                "guessed letters:\n{guessed}\n\nunused letters:\n{unused}",
+                guessed = guesses.letter_states().as_emoji(),
                 unused = guesses.unused_letters().as_emoji()
             );
 
@@ -319,5 +901,412 @@ async fn unused(ctx: Context<'_>) -> Result<()> {
 
     result?;
 
+    Ok(())
+} */
+
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn unused(ctx: Context<'_>) -> Result<()> {
+    let result: CommandResult = try {
+        let wordle = ctx.data().wordle();
+
+        if let Some(unused) = wordle.unused_letters(ctx.channel_id()).await {
+            let response = format!("unused letters:\n{unused}", unused = unused.as_emoji());
+
+            ctx.reply(response).await?;
+        } else {
+            ctx.reply_ephemeral("there isn't a game active in this channel!")
+                .await?;
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// get a hint for the game in this channel
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn hint(
+    ctx: Context<'_>,
+    #[description = "what kind of hint to get (defaults to how many answers are left)"]
+    kind: Option<HintKind>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        let wordle = ctx.data().wordle();
+
+        if let Some(hint) = wordle
+            .hint(ctx.channel_id(), kind.unwrap_or_default())
+            .await
+        {
+            ctx.reply_ephemeral(hint).await?;
+        } else {
+            ctx.reply_ephemeral("there isn't a game active in this channel!")
+                .await?;
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// race other players to solve the same wordle
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn race(
+    ctx: Context<'_>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+    #[description = "how many letters the word has (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        let hard_mode = hard_mode.unwrap_or_default();
+
+        with_length!(ctx, length.unwrap_or(5), race_game(ctx, hard_mode));
+    };
+
+    result?;
+
+    Ok(())
+}
+
+async fn race_game<const N: usize>(ctx: Context<'_>, hard_mode: bool) -> CommandResult
+where
+    WordleData: HasLength<N>,
+{
+    let Some(length) = length_or_reply::<N>(ctx).await? else {
+        return Ok(());
+    };
+
+    wordle::race::run(ctx, length.words(), hard_mode).await?;
+
+    Ok(())
+}
+
+/// guess several random words at once
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn multi(
+    ctx: Context<'_>,
+    #[description = "how many words to guess (defaults to 2)"] boards: Option<BoardCount>,
+    style: Option<GameStyle>,
+    #[description = "how many letters the words have (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        if channel_busy(ctx).await? {
+            return Ok(());
+        }
+
+        let boards = boards.unwrap_or_default();
+
+        let options = GameOptionsBuilder::default()
+            .style(style)
+            .guesses_limit(boards.guesses_limit())
+            .build();
+
+        with_length!(
+            ctx,
+            length.unwrap_or(5),
+            multi_game(ctx, options, boards.count())
+        );
+    };
+
+    result?;
+
+    Ok(())
+}
+
+async fn multi_game<const N: usize>(
+    ctx: Context<'_>,
+    options: GameOptions,
+    boards: usize,
+) -> CommandResult
+where
+    WordleData: HasLength<N>,
+{
+    let Some(length) = length_or_reply::<N>(ctx).await? else {
+        return Ok(());
+    };
+
+    let Some(puzzle) = wordle::Puzzle::multi(length.words(), boards) else {
+        ctx.reply_ephemeral(format!(
+            "there aren't enough {N}-letter words for {boards} boards!"
+        ))
+        .await?;
+
+        return Ok(());
+    };
+
+    let mut game = wordle::Game::new(ctx, puzzle, options).await?;
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(())
+}
+
+/// play a wordle that avoids your guesses for as long as it can
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn absurdle(
+    ctx: Context<'_>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+    #[description = "how many letters the word has (defaults to 5)"]
+    #[min = 4]
+    #[max = 7]
+    length: Option<usize>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        if channel_busy(ctx).await? {
+            return Ok(());
+        }
+
+        let options = GameOptionsBuilder::default()
+            .style(style)
+            .hard_mode(hard_mode)
+            .build();
+
+        with_length!(ctx, length.unwrap_or(5), absurdle_game(ctx, options));
+    };
+
+    result?;
+
+    Ok(())
+}
+
+async fn absurdle_game<const N: usize>(ctx: Context<'_>, options: GameOptions) -> CommandResult
+where
+    WordleData: HasLength<N>,
+{
+    let Some(length) = length_or_reply::<N>(ctx).await? else {
+        return Ok(());
+    };
+
+    let puzzle = wordle::Puzzle::absurdle(length.words());
+    let mut game = wordle::Game::new(ctx, puzzle, options).await?;
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(())
+}
+
+/// guess a word in the game in this channel
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn guess(ctx: Context<'_>, #[description = "the word to guess"] word: String) -> Result<()> {
+    let result: CommandResult = try {
+        let wordle = ctx.data().wordle();
+
+        match wordle
+            .guess(
+                ctx.channel_id(),
+                ctx.author().clone(),
+                word.clone(),
+                core::sent_at(ctx.id()),
+            )
+            .await
+        {
+            Some(Ok(())) => {
+                ctx.reply_ephemeral(format!("guessed `{word}`!")).await?;
+            }
+            Some(Err(err)) => {
+                ctx.reply_ephemeral(err.to_string()).await?;
+            }
+            None => {
+                ctx.reply_ephemeral("there isn't a game active in this channel!")
+                    .await?;
+            }
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// challenge someone to guess a word you pick
+#[instrument(skip_all)]
+#[poise::command(
+    // a prefix command would show the word to the whole channel
+    slash_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn challenge(
+    ctx: Context<'_>,
+    #[description = "who to challenge"] user: User,
+    #[description = "the word they have to guess"] word: String,
+) -> Result<()> {
+    let result: CommandResult = try {
+        let wordle = ctx.data().wordle();
+
+        if user.bot || user.id == ctx.author().id {
+            ctx.reply_ephemeral("you can only challenge someone else!")
+                .await?;
+            return Ok(());
+        }
+
+        let answer = match wordle::game::check(wordle.words(), &word, None) {
+            Ok(answer) => answer,
+            Err(wordle::game::GuessError::NotInList) => {
+                ctx.reply_ephemeral("that isn't in the words list!").await?;
+                return Ok(());
+            }
+            Err(_) => {
+                ctx.reply_ephemeral("challenges have to be a 5-letter word!")
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let challenge = wordle
+            .challenges()
+            .create(ctx.author().id, user.id, &answer)
+            .await?;
+
+        let invite = CreateMessage::new().content(format!(
+            "`{name}` challenged you to a wordle! play it with `/wordle accept {number}`",
+            name = ctx.author().name,
+            number = challenge.number
+        ));
+
+        if user.dm(ctx, invite).await.is_ok() {
+            ctx.reply_ephemeral(format!(
+                "sent challenge {number} to `{name}`!",
+                number = challenge.number,
+                name = user.name
+            ))
+            .await?;
+        } else {
+            ctx.reply_ephemeral(format!(
+                "couldn't dm `{name}`, but they can still play it with `/wordle accept {number}`",
+                number = challenge.number,
+                name = user.name
+            ))
+            .await?;
+        }
+    };
+
+    result?;
+
+    Ok(())
+}
+
+/// play a wordle someone challenged you to
+#[instrument(skip_all)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    discard_spare_arguments,
+    required_bot_permissions = "SEND_MESSAGES | VIEW_CHANNEL"
+)]
+async fn accept(
+    ctx: Context<'_>,
+    #[description = "the challenge's number (defaults to your oldest one)"] number: Option<u32>,
+    style: Option<GameStyle>,
+    #[description = "green letters must stay in place and yellow letters must be reused"]
+    hard_mode: Option<bool>,
+) -> Result<()> {
+    let result: CommandResult = try {
+        let wordle = ctx.data().wordle();
+
+        let challenge = match number {
+            Some(number) => wordle.challenges().find(number).await?,
+            None => wordle.challenges().next_for(ctx.author().id).await?,
+        };
+
+        let Some(challenge) = challenge.filter(|challenge| challenge.target == ctx.author().id)
+        else {
+            ctx.reply_ephemeral(match number {
+                Some(_) => "that challenge isn't for you!",
+                None => "nobody has challenged you yet!",
+            })
+            .await?;
+            return Ok(());
+        };
+
+        if challenge.is_finished() {
+            ctx.reply_ephemeral(format!(
+                "you've already played challenge {}!",
+                challenge.number
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        if channel_busy(ctx).await? {
+            return Ok(());
+        }
+
+        let Some(puzzle) = challenge.puzzle(wordle.words()) else {
+            ctx.reply_ephemeral("that challenge's word isn't in the words list anymore!")
+                .await?;
+            return Ok(());
+        };
+
+        // an unfinished attempt picks up where it was left off
+        let paused = challenge.game.as_ref().filter(|game| game.in_progress());
+
+        let mut game = wordle::Game::new(
+            ctx,
+            puzzle,
+            GameOptionsBuilder::default()
+                .style(style)
+                .hard_mode(hard_mode.or(paused.map(|game| game.hard_mode)))
+                .build(),
+        )
+        .await?;
+
+        if let Some(paused) = paused {
+            game.resume(paused);
+        }
+
+        game.setup().await?;
+        game.play_out().await?;
+    };
+
+    result?;
+
     Ok(())
 }
@@ -0,0 +1,109 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use super::solver::{self, Pattern};
+
+/// A puzzle that never picks an answer up front. Each guess gets the colors
+/// that leave the most answers open, until only one is left and it's guessed.
+#[derive(Debug, Clone)]
+pub struct Absurdle<const N: usize = 5> {
+    remaining: Vec<String>,
+    /// One of the remaining answers; every one of them colors the guesses so far the same way.
+    answer: kwordle::Word<N>,
+}
+
+impl<const N: usize> Absurdle<N> {
+    pub fn new(words: &kwordle::WordsList<N>) -> Self {
+        Self {
+            remaining: words.answers.iter().map(ToString::to_string).collect(),
+            answer: words.answers.random(),
+        }
+    }
+
+    /// Narrows down the answers as little as possible for a guess.
+    pub fn respond(&self, guess: &kwordle::Word<N>, words: &kwordle::WordsList<N>) -> Self {
+        let guess: Vec<char> = guess.to_string().chars().collect();
+        let remaining = largest_group(&guess, &self.remaining);
+
+        let answer = remaining
+            .first()
+            .and_then(|answer| kwordle::Word::from_str(words, answer).ok())
+            .unwrap_or(self.answer);
+
+        Self { remaining, answer }
+    }
+
+    pub fn answer(&self) -> &kwordle::Word<N> {
+        &self.answer
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Whether there's only one answer left.
+    pub fn is_fixed(&self) -> bool {
+        self.remaining() == 1
+    }
+}
+
+/// The candidates that share the most common pattern for a guess.
+/// Ties go to the lowest pattern, so they're settled the same way every time.
+fn largest_group(guess: &[char], candidates: &[String]) -> Vec<String> {
+    let mut groups: HashMap<Pattern, Vec<String>> = HashMap::new();
+
+    for candidate in candidates {
+        let answer: Vec<char> = candidate.chars().collect();
+
+        groups
+            .entry(solver::pattern(&answer, guess))
+            .or_default()
+            .push(candidate.clone());
+    }
+
+    groups
+        .into_iter()
+        .max_by_key(|(pattern, group)| (group.len(), Reverse(*pattern)))
+        .map(|(_, group)| group)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::largest_group;
+    use pretty_assertions::assert_eq;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| (*word).to_owned()).collect()
+    }
+
+    #[test]
+    fn keeps_most_answers() {
+        assert_eq!(
+            largest_group(
+                &chars("crane"),
+                &strings(&["chair", "pupil", "onion", "moldy"])
+            ),
+            strings(&["pupil", "moldy"])
+        );
+    }
+
+    #[test]
+    fn ties_reveal_least() {
+        assert_eq!(
+            largest_group(&chars("crane"), &strings(&["chair", "onion", "pupil"])),
+            strings(&["pupil"])
+        );
+    }
+
+    #[test]
+    fn last_answer() {
+        assert_eq!(
+            largest_group(&chars("onion"), &strings(&["onion"])),
+            strings(&["onion"])
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use mongodb::{bson::doc, options::ReplaceOptions, Collection, Database};
+use poise::serenity_prelude::{
+    futures::TryStreamExt, CacheHttp, ChannelId, EditMessage, GuildId, MessageId, UserId,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    daily::daily_command, game::options::GameOptions, puzzle::SavedPuzzle, DbResult, Language,
+    Puzzle, Timer,
+};
+
+/// A game that was running in a channel, kept in the database so it can be
+/// picked back up if the bot restarts while it's being played.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveGame {
+    pub channel: ChannelId,
+    pub message: MessageId,
+    daily: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    challenge: Option<u32>,
+    answer: String,
+    /// Games saved before they could be picked back up don't have this, and can only be expired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    game: Option<SavedGame>,
+}
+
+/// Everything a running game needs to be picked back up on its message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub owner: UserId,
+    /// Where the game was started, which decides whose dailies it plays.
+    pub guild: Option<GuildId>,
+    pub length: usize,
+    #[serde(default)]
+    pub language: Language,
+    pub puzzle: SavedPuzzle,
+    pub options: GameOptions,
+    pub guesses: Vec<String>,
+    pub authors: Vec<UserId>,
+    pub hints: usize,
+    pub timer: Option<Timer>,
+    /// The message mirroring the board, if the game is being spectated.
+    pub spectator: Option<(ChannelId, MessageId)>,
+}
+
+impl ActiveGame {
+    pub fn new<const N: usize>(
+        channel: ChannelId,
+        message: MessageId,
+        puzzle: &Puzzle<N>,
+        game: SavedGame,
+    ) -> Self {
+        Self {
+            channel,
+            message,
+            daily: puzzle.number(),
+            challenge: puzzle.challenge_number(),
+            answer: puzzle
+                .answers()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            game: Some(game),
+        }
+    }
+
+    pub fn saved(&self) -> Option<&SavedGame> {
+        self.game.as_ref()
+    }
+
+    /// `dailies` is the server whose dailies the game was from, or `None` for the shared ones.
+    fn expired_text(&self, dailies: Option<GuildId>) -> String {
+        if let Some(number) = self.challenge {
+            return format!(
+                "\nchallenge {number} was paused when the bot restarted! continue it with `/wordle accept {number}`"
+            );
+        }
+
+        match self.daily {
+            Some(number) => format!(
+                "\nwordle {number} was paused when the bot restarted! continue it with {command}",
+                command = daily_command(
+                    self.saved().map(|game| game.language).unwrap_or_default(),
+                    dailies
+                )
+            ),
+            None if self.answer.is_empty() => {
+                "\nthis game expired when the bot restarted!".to_owned()
+            }
+            None if self.answer.contains(',') => format!(
+                "\nthis game expired when the bot restarted! the words were: {answers}",
+                answers = self.answer
+            ),
+            None => format!(
+                "\nthis game expired when the bot restarted! the word was: {answer}",
+                answer = self.answer
+            ),
+        }
+    }
+
+    /// Removes the buttons from the game message and explains what happened to the game.
+    pub async fn expire(
+        &self,
+        cache_http: impl CacheHttp,
+        dailies: Option<GuildId>,
+    ) -> poise::serenity_prelude::Result<()> {
+        let message = self.channel.message(&cache_http, self.message).await?;
+
+        self.channel
+            .edit_message(
+                &cache_http,
+                self.message,
+                EditMessage::new()
+                    .content(message.content + &self.expired_text(dailies))
+                    .components(Vec::new()),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveGames {
+    collection: Collection<ActiveGame>,
+}
+
+impl ActiveGames {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("active_wordles"),
+        }
+    }
+
+    pub async fn all(&self) -> DbResult<Vec<ActiveGame>> {
+        self.collection.find(None, None).await?.try_collect().await
+    }
+
+    pub async fn save(&self, game: &ActiveGame) -> DbResult<()> {
+        let channel = mongodb::bson::ser::to_bson(&game.channel).expect("implements serialize");
+
+        self.collection
+            .replace_one(
+                doc! { "channel": channel },
+                game,
+                ReplaceOptions::builder().upsert(true).build(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Takes a saved game out of the database, so only one restore can pick it back up.
+    /// Gives nothing if something else already took it.
+    pub async fn claim(&self, channel: ChannelId) -> DbResult<Option<ActiveGame>> {
+        let channel = mongodb::bson::ser::to_bson(&channel).expect("implements serialize");
+
+        self.collection
+            .find_one_and_delete(doc! { "channel": channel }, None)
+            .await
+    }
+
+    pub async fn remove(&self, channel: ChannelId) -> DbResult<()> {
+        let channel = mongodb::bson::ser::to_bson(&channel).expect("implements serialize");
+
+        self.collection
+            .delete_one(doc! { "channel": channel }, None)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+
+use super::solver::{self, Revealed};
+
+/// How one guess in a game went, compared to the best guess at that point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub word: String,
+    /// Answers that were still possible before the guess.
+    pub before: usize,
+    /// Answers that were still possible after the guess.
+    pub after: usize,
+    /// How many answers the guess was expected to leave.
+    pub expected: f64,
+    pub best: String,
+    /// How many answers the best guess was expected to leave.
+    pub best_expected: f64,
+    /// The share of possible answers that would have left more answers than this one did,
+    /// counting ties as half.
+    pub luck: f64,
+}
+
+impl Step {
+    /// Out of 100, where 100 means the guess was as good as the best one.
+    pub fn skill(&self) -> u32 {
+        if self.expected <= 0.0 {
+            return 100;
+        }
+
+        ((self.best_expected / self.expected * 100.0).round() as u32).min(100)
+    }
+
+    /// Out of 100, where 50 means the guess left as many answers as it should have.
+    pub fn luck(&self) -> u32 {
+        (self.luck * 100.0).round() as u32
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{word}` skill **{skill}** · luck **{luck}** · {before} → {after} answers left",
+            word = self.word.to_ascii_uppercase(),
+            skill = self.skill(),
+            luck = self.luck(),
+            before = self.before,
+            after = self.after,
+        )?;
+
+        if self.best != self.word {
+            write!(f, " (best: `{}`)", self.best.to_ascii_uppercase())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Analyzes each guess of a game, given every answer the game could have had.
+pub fn analyze(revealed: &[Revealed], answers: &[String]) -> Vec<Step> {
+    let mut candidates: Vec<Vec<char>> = answers
+        .iter()
+        .map(|answer| answer.chars().collect())
+        .collect();
+
+    let mut steps = Vec::with_capacity(revealed.len());
+
+    for guess in revealed {
+        let Some(best) = solver::best_index(&candidates) else {
+            break;
+        };
+
+        let before = candidates.len();
+        let groups = solver::groups(guess.word(), &candidates);
+        let after = groups.get(&guess.pattern()).copied().unwrap_or_default();
+
+        let luckier: usize = groups
+            .values()
+            .map(|&size| match size.cmp(&after) {
+                std::cmp::Ordering::Greater => size * 2,
+                std::cmp::Ordering::Equal => size,
+                std::cmp::Ordering::Less => 0,
+            })
+            .sum();
+
+        steps.push(Step {
+            word: guess.word().iter().collect(),
+            before,
+            after,
+            expected: solver::expected_remaining(guess.word(), &candidates),
+            best: candidates[best].iter().collect(),
+            best_expected: solver::expected_remaining(&candidates[best], &candidates),
+            luck: luckier as f64 / (before * 2) as f64,
+        });
+
+        candidates.retain(|answer| solver::pattern(answer, guess.word()) == guess.pattern());
+    }
+
+    steps
+}
+
+/// Analyzes a game played with a words list.
+/// The game's answer counts as a possible answer even if it isn't one of the list's,
+/// like a challenge word picked from the guesses.
+pub fn analyze_guesses<const N: usize>(
+    guesses: &kwordle::Guesses<N>,
+    words: &kwordle::WordsList<N>,
+    answer: &kwordle::Word<N>,
+) -> Vec<Step> {
+    let revealed: Vec<Revealed> = guesses
+        .iter()
+        .map(|guess| Revealed::from_guess(&guess))
+        .collect();
+
+    let mut answers: Vec<String> = words.answers.iter().map(ToString::to_string).collect();
+    let answer = answer.to_string();
+
+    if !answers.contains(&answer) {
+        answers.push(answer);
+    }
+
+    analyze(&revealed, &answers)
+}
+
+pub fn summary(steps: &[Step]) -> String {
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| format!("{number}. {step}", number = index + 1))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, analyze_guesses};
+    use crate::commands::wordle::core::solver::{pattern, Revealed};
+    use pretty_assertions::assert_eq;
+
+    fn game(answer: &str, guesses: &[&str]) -> Vec<Revealed> {
+        let answer: Vec<char> = answer.chars().collect();
+
+        guesses
+            .iter()
+            .map(|guess| {
+                let word: Vec<char> = guess.chars().collect();
+                Revealed::new(guess, pattern(&answer, &word))
+            })
+            .collect()
+    }
+
+    fn answers() -> Vec<String> {
+        ["bills", "fills", "hills", "pills", "flips"]
+            .map(str::to_owned)
+            .to_vec()
+    }
+
+    #[test]
+    fn steps() {
+        let steps = analyze(&game("hills", &["bills", "hills"]), &answers());
+
+        assert_eq!(steps.len(), 2);
+
+        assert_eq!((steps[0].before, steps[0].after), (5, 3));
+        assert_eq!(steps[0].best, "flips");
+        assert_eq!(steps[0].skill(), 64);
+        assert_eq!(steps[0].luck(), 30);
+
+        assert_eq!((steps[1].before, steps[1].after), (3, 1));
+        assert_eq!(steps[1].skill(), 100);
+        assert_eq!(steps[1].luck(), 83);
+    }
+
+    #[test]
+    fn best_guess_skill() {
+        let steps = analyze(&game("fills", &["flips"]), &answers());
+
+        assert_eq!(steps[0].skill(), 100);
+        assert_eq!(steps[0].after, 1);
+    }
+
+    #[test]
+    fn answer_outside_answers() {
+        let guesses: Vec<String> = ["bills", "fills", "hills", "pills", "flips", "chill"]
+            .map(str::to_owned)
+            .to_vec();
+        let words = kwordle::WordsList::<5>::from_strs(&guesses, &answers()).unwrap();
+        let answer = kwordle::Word::from_str(&words, "chill").unwrap();
+
+        let mut played = kwordle::Guesses::new(None);
+        played.push(answer.guess(kwordle::Word::from_str(&words, "bills").unwrap()));
+        played.push(answer.guess(answer));
+
+        let steps = analyze_guesses(&played, &words, &answer);
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!((steps[0].before, steps[0].after), (6, 1));
+        assert_eq!((steps[1].before, steps[1].after), (1, 1));
+    }
+}
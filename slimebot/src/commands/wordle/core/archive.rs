@@ -0,0 +1,75 @@
+use mongodb::{bson::doc, options::ReplaceOptions, Collection, Database};
+use poise::serenity_prelude::{GuildId, UserId};
+use serde::{Deserialize, Serialize};
+
+use super::{DbResult, GameRecord};
+
+/// A game of a past daily wordle, played after it expired.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchivedGame<const N: usize = 5> {
+    number: u32,
+    /// The server whose daily this was, if it had its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    guild: Option<GuildId>,
+    #[serde(flatten)]
+    game: GameRecord<N>,
+}
+
+/// Games of expired daily wordles, kept apart from the dailies
+/// so they don't count toward stats, streaks or leaderboards.
+#[derive(Debug, Clone)]
+pub struct ArchivedGames {
+    collection: Collection<ArchivedGame>,
+}
+
+impl ArchivedGames {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("archived_wordles"),
+        }
+    }
+
+    pub async fn update<const N: usize>(
+        &self,
+        guild: Option<GuildId>,
+        number: u32,
+        game: GameRecord<N>,
+    ) -> DbResult<()> {
+        let user = mongodb::bson::ser::to_bson(&game.user).expect("implements serialize");
+        let guild_bson = mongodb::bson::ser::to_bson(&guild).expect("implements serialize");
+
+        self.collection
+            .clone_with_type::<ArchivedGame<N>>()
+            .replace_one(
+                doc! { "number": number, "guild": guild_bson, "user": user },
+                ArchivedGame {
+                    number,
+                    guild,
+                    game,
+                },
+                ReplaceOptions::builder().upsert(true).build(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_game(
+        &self,
+        guild: Option<GuildId>,
+        user: UserId,
+        number: u32,
+    ) -> DbResult<Option<GameRecord>> {
+        let user = mongodb::bson::ser::to_bson(&user).expect("implements serialize");
+        let guild = mongodb::bson::ser::to_bson(&guild).expect("implements serialize");
+
+        Ok(self
+            .collection
+            .find_one(
+                doc! { "number": number, "guild": guild, "user": user },
+                None,
+            )
+            .await?
+            .map(|archived| archived.game))
+    }
+}
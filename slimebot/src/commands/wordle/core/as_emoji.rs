@@ -18,7 +18,8 @@ pub trait AsEmoji {
 
     fn emoji_with_style(&self, style: GameStyle) -> Cow<str> {
         match style {
-            GameStyle::Colors => self.as_emoji(),
+            // images are drawn separately; this is for when a picture won't do
+            GameStyle::Colors | GameStyle::Image => self.as_emoji(),
             GameStyle::Letters => self.emoji_with_letters().into(),
             GameStyle::SpacedLetters => self.emoji_with_letters_spaced().into(),
         }
@@ -35,12 +36,16 @@ impl AsEmoji for char {
         let alphabet_letters = 'a'..='z';
         let emoji_letters = '🇦'..='🇿';
 
-        let emoji = alphabet_letters
-            .zip(emoji_letters)
-            .find_map(|(letter, emoji)| (*self == letter).then_some(emoji))
-            .expect("char should be alphabetic");
+        let letter = self.to_ascii_lowercase();
 
-        emoji.to_string().into()
+        // letters like `ñ` don't have an emoji, so they're shown as they are
+        alphabet_letters
+            .zip(emoji_letters)
+            .find_map(|(alphabet, emoji)| (letter == alphabet).then_some(emoji))
+            .map_or_else(
+                || self.to_uppercase().to_string().into(),
+                |emoji| emoji.to_string().into(),
+            )
     }
 }
 
@@ -84,7 +89,7 @@ impl AsEmoji for Vec<kwordle::LetterState> {
     }
 }
 
-impl AsEmoji for kwordle::Guess {
+impl<const N: usize> AsEmoji for kwordle::Guess<N> {
     fn as_emoji(&self) -> Cow<str> {
         self.into_iter()
             .map(|(_, state)| state)
@@ -123,7 +128,7 @@ impl AsEmoji for kwordle::Guess {
     }
 }
 
-impl AsEmoji for kwordle::Guesses {
+impl<const N: usize> AsEmoji for kwordle::Guesses<N> {
     fn as_emoji(&self) -> Cow<str> {
         self.iter()
             .map(|g| g.as_emoji())
@@ -147,7 +152,7 @@ impl AsEmoji for kwordle::Guesses {
     }
 }
 
-impl AsEmoji for Vec<kwordle::Guess> {
+impl<const N: usize> AsEmoji for Vec<kwordle::Guess<N>> {
     fn as_emoji(&self) -> Cow<str> {
         self.iter()
             .map(|g| g.as_emoji())
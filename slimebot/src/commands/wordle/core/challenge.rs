@@ -0,0 +1,212 @@
+use chrono::Utc;
+use mongodb::{
+    bson::doc,
+    options::{FindOneAndUpdateOptions, FindOneOptions, ReturnDocument},
+    Collection, Database,
+};
+use poise::serenity_prelude::UserId;
+use serde::{Deserialize, Serialize};
+
+use crate::framework::data::UtcDateTime;
+
+use super::{puzzle::ChallengePuzzle, DbResult, GameRecord, Puzzle};
+
+/// A word one user picked for another to guess, and how they did.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Challenge {
+    pub number: u32,
+    pub challenger: UserId,
+    pub target: UserId,
+    answer: String,
+    pub sent: UtcDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game: Option<GameRecord>,
+}
+
+impl Challenge {
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    /// The challenge as a puzzle, if its word is still in the words list.
+    pub fn puzzle(&self, words: &kwordle::WordsList<5>) -> Option<Puzzle> {
+        let answer = kwordle::Word::from_str(words, &self.answer).ok()?;
+
+        Some(Puzzle::Challenge(ChallengePuzzle::new(
+            self.number,
+            self.challenger,
+            answer,
+        )))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.game.as_ref().is_some_and(GameRecord::is_finished)
+    }
+
+    /// Says how the challenge went, for the user who sent it.
+    pub fn result(&self, target: &str) -> Option<String> {
+        let game = self.game.as_ref().filter(|game| game.is_finished())?;
+
+        Some(if game.is_solved() {
+            format!(
+                "`{target}` solved your challenge ({answer}) in {guesses}!",
+                answer = self.answer,
+                guesses = game.num_guesses
+            )
+        } else {
+            format!(
+                "`{target}` didn't solve your challenge ({answer})",
+                answer = self.answer
+            )
+        })
+    }
+}
+
+/// The last number given out, kept in a document of its own
+/// so that challenges sent at the same time can't get the same number.
+#[derive(Debug, Deserialize)]
+struct Counter {
+    number: u32,
+}
+
+/// Challenges sent between users, numbered in the order they were sent.
+#[derive(Debug, Clone)]
+pub struct Challenges {
+    collection: Collection<Challenge>,
+    counters: Collection<Counter>,
+}
+
+impl Challenges {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("wordle_challenges"),
+            counters: db.collection("wordle_counters"),
+        }
+    }
+
+    /// Takes the next challenge number.
+    async fn next_number(&self) -> DbResult<u32> {
+        // challenges sent before there was a counter start it off from the latest one
+        let latest = self
+            .collection
+            .find_one(
+                None,
+                FindOneOptions::builder()
+                    .sort(doc! { "number": -1 })
+                    .build(),
+            )
+            .await?
+            .map_or(0, |challenge| challenge.number);
+
+        let counter = self
+            .counters
+            .find_one_and_update(
+                doc! { "_id": "challenges" },
+                vec![doc! {
+                    "$set": { "number": { "$add": [{ "$ifNull": ["$number", latest] }, 1] } }
+                }],
+                FindOneAndUpdateOptions::builder()
+                    .upsert(true)
+                    .return_document(ReturnDocument::After)
+                    .build(),
+            )
+            .await?
+            .expect("upserting should give back the counter");
+
+        Ok(counter.number)
+    }
+
+    pub async fn create(
+        &self,
+        challenger: UserId,
+        target: UserId,
+        answer: &kwordle::Word<5>,
+    ) -> DbResult<Challenge> {
+        let challenge = Challenge {
+            number: self.next_number().await?,
+            challenger,
+            target,
+            answer: answer.to_string(),
+            sent: Utc::now(),
+            game: None,
+        };
+
+        self.collection.insert_one(&challenge, None).await?;
+
+        Ok(challenge)
+    }
+
+    pub async fn find(&self, number: u32) -> DbResult<Option<Challenge>> {
+        self.collection
+            .find_one(doc! { "number": number }, None)
+            .await
+    }
+
+    /// The oldest challenge the user hasn't finished yet.
+    pub async fn next_for(&self, target: UserId) -> DbResult<Option<Challenge>> {
+        let target = mongodb::bson::ser::to_bson(&target).expect("implements serialize");
+
+        self.collection
+            .find_one(
+                doc! { "target": target, "game.finished": { "$ne": true } },
+                FindOneOptions::builder().sort(doc! { "number": 1 }).build(),
+            )
+            .await
+    }
+
+    pub async fn update<const N: usize>(&self, number: u32, game: GameRecord<N>) -> DbResult<()> {
+        let game = mongodb::bson::ser::to_bson(&game).expect("implements serialize");
+
+        self.collection
+            .update_one(
+                doc! { "number": number },
+                doc! { "$set": { "game": game } },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    use super::{Challenge, GameRecord};
+
+    fn challenge(game: Option<GameRecord>) -> Challenge {
+        Challenge {
+            number: 1,
+            challenger: UserId::new(1),
+            target: UserId::new(2),
+            answer: "onion".to_owned(),
+            sent: Utc::now(),
+            game,
+        }
+    }
+
+    #[test]
+    fn result() {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let mut guesses = kwordle::Guesses::new(Some(6));
+        guesses.push(answer.guess(kwordle::Word::from_str(&words, "chair").unwrap()));
+
+        assert_eq!(challenge(None).result("slime"), None);
+
+        let unfinished = GameRecord::new(UserId::new(2), guesses.clone(), false, false);
+        assert_eq!(challenge(Some(unfinished)).result("slime"), None);
+
+        guesses.push(answer.guess(answer));
+
+        let solved = GameRecord::new(UserId::new(2), guesses, true, false);
+        assert_eq!(
+            challenge(Some(solved)).result("slime").as_deref(),
+            Some("`slime` solved your challenge (onion) in 2!")
+        );
+    }
+}
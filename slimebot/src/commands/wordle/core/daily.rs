@@ -2,26 +2,64 @@ use std::ops::Not;
 
 use chrono::Utc;
 use mongodb::{
-    bson::doc,
+    bson::{doc, Document},
     options::{FindOneOptions, FindOptions},
     Collection, Database,
 };
 use poise::serenity_prelude::{
-    futures::{Stream, StreamExt},
-    UserId,
+    futures::{Stream, StreamExt, TryStreamExt},
+    GuildId, UserId,
 };
 use serde::{Deserialize, Serialize};
-use tracing::{debug, instrument, trace};
+use tracing::{debug, error, instrument, trace};
 
-use super::{puzzle, DbResult, GameRecord};
+use super::{
+    puzzle, DbResult, GameRecord, Language, LeaderboardEntry, LeaderboardPeriod, Schedule,
+    UserStats,
+};
+
+/// How to carry on with a paused daily in `language`,
+/// from the server whose dailies it is, or `None` for the shared ones.
+pub fn daily_command(language: Language, guild: Option<GuildId>) -> String {
+    match guild {
+        _ if !language.is_english() => {
+            format!("`/wordle daily language:{}`", language.name())
+        }
+        // the game is in DMs, where `/wordle daily` would start the shared daily
+        Some(_) => "`/wordle daily` in the server it's from".to_owned(),
+        None => "`/wordle daily`".to_owned(),
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct DailyWordles {
     collection: Collection<PartialDailyWordle>,
-    words_list: kwordle::WordsList,
+    schedule: Schedule,
+    /// The server these dailies belong to, or `None` for the ones every server shares.
+    guild: Option<GuildId>,
 }
 
 impl DailyWordles {
+    /// Limits a query to this server's dailies.
+    /// Dailies from before servers could have their own don't have a guild, so they match `null`.
+    fn scope(&self, mut filter: Document) -> Document {
+        let guild = mongodb::bson::ser::to_bson(&self.guild).expect("implements serialize");
+        filter.insert("guild", guild);
+        filter
+    }
+
+    fn parse(&self, partial: PartialDailyWordle) -> Option<DailyWordle> {
+        let number = partial.puzzle.number;
+
+        let daily = DailyWordle::from_partial(partial);
+
+        if daily.is_none() {
+            error!(number, "daily wordle answer isn't a valid word");
+        }
+
+        daily
+    }
+
     async fn find_one(
         &self,
         filter: impl Into<Option<mongodb::bson::Document>>,
@@ -29,9 +67,9 @@ impl DailyWordles {
     ) -> DbResult<Option<DailyWordle>> {
         Ok(self
             .collection
-            .find_one(filter, options)
+            .find_one(self.scope(filter.into().unwrap_or_default()), options)
             .await?
-            .and_then(|partial| DailyWordle::from_partial(partial, &self.words_list)))
+            .and_then(|partial| self.parse(partial)))
     }
 
     async fn find(
@@ -39,21 +77,50 @@ impl DailyWordles {
         filter: impl Into<Option<mongodb::bson::Document>>,
         options: impl Into<Option<FindOptions>>,
     ) -> DbResult<impl Stream<Item = DbResult<DailyWordle>> + '_> {
-        Ok(self.collection.find(filter, options).await?.map(|res| {
-            res.map(|partial| {
-                DailyWordle::from_partial(partial, &self.words_list)
-                    .expect("should be a valid daily")
-            })
-        }))
+        Ok(self
+            .collection
+            .find(self.scope(filter.into().unwrap_or_default()), options)
+            .await?
+            .filter_map(|res| {
+                std::future::ready(res.map(|partial| self.parse(partial)).transpose())
+            }))
+    }
+
+    pub fn new(db: &Database, schedule: Schedule) -> Self {
+        Self::in_collection(db, "daily_wordles", schedule)
+    }
+
+    /// Dailies kept apart from the usual ones, like for another language.
+    pub fn in_collection(db: &Database, collection: &str, schedule: Schedule) -> Self {
+        Self {
+            collection: db.collection(collection),
+            schedule,
+            guild: None,
+        }
     }
 
-    pub fn new(db: &Database, words: &kwordle::WordsList) -> Self {
+    /// The same dailies, but a separate sequence of them just for one server.
+    pub fn in_guild(&self, guild: GuildId) -> Self {
         Self {
-            collection: db.collection("daily_wordles"),
-            words_list: words.clone(),
+            guild: Some(guild),
+            ..self.clone()
         }
     }
 
+    pub const fn guild(&self) -> Option<GuildId> {
+        self.guild
+    }
+
+    pub const fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Hours left until the next daily wordle, rounded down.
+    pub fn hours_until_rollover(&self) -> i64 {
+        let now = Utc::now();
+        (self.schedule.next_rollover(now) - now).num_hours()
+    }
+
     #[instrument(skip_all)]
     pub async fn latest(&self) -> DbResult<Option<DailyWordle>> {
         let daily = self
@@ -78,21 +145,19 @@ impl DailyWordles {
         Ok(self
             .latest()
             .await?
-            .filter(|daily| daily.is_expired().not()))
+            .filter(|daily| daily.is_expired(&self.schedule).not()))
     }
 
+    /// Starts a new daily wordle, unless one has already started since the last rollover.
     pub async fn refresh(&self, words: &kwordle::WordsList) -> DbResult<Option<DailyWordle>> {
-        let new_word = words.answers.random();
-
-        if let Some(latest) = self.latest_not_expired().await? {
-            if latest.is_old() {
-                return self.new_daily(&new_word).await.map(Some);
-            }
-        } else {
-            return self.new_daily(&new_word).await.map(Some);
+        if let Some(latest) = self.latest_not_expired().await?
+            && latest.is_recent(&self.schedule)
+        {
+            return Ok(None);
         }
 
-        Ok(None)
+        let new_word = words.answers.random();
+        self.new_daily(&new_word).await.map(Some)
     }
 
     pub async fn new_daily(&self, word: &kwordle::Word<5>) -> DbResult<DailyWordle> {
@@ -101,7 +166,7 @@ impl DailyWordles {
         debug!(latest_number);
 
         let puzzle = puzzle::DailyPuzzle::new(latest_number + 1, *word);
-        let wordle = DailyWordle::new(puzzle);
+        let wordle = DailyWordle::new(puzzle, self.guild);
 
         self.collection
             .insert_one(&wordle.clone().into_partial(), None)
@@ -110,7 +175,7 @@ impl DailyWordles {
         Ok(wordle)
     }
 
-    pub async fn update(&self, puzzle: u32, game: GameRecord) -> DbResult<()> {
+    pub async fn update<const N: usize>(&self, puzzle: u32, game: GameRecord<N>) -> DbResult<()> {
         let user = mongodb::bson::ser::to_bson(&game.user).expect("implements serialize");
         let game = mongodb::bson::ser::to_bson(&game).expect("implements serialize");
 
@@ -129,10 +194,10 @@ impl DailyWordles {
 
             self.collection
                 .update_one(
-                    doc! {
+                    self.scope(doc! {
                         "puzzle.number": puzzle,
                         "games": { "$elemMatch": { "user": &user } }
-                    },
+                    }),
                     doc! { "$set": { "games.$": game } },
                     None,
                 )
@@ -142,7 +207,7 @@ impl DailyWordles {
 
             self.collection
                 .update_one(
-                    doc! { "puzzle.number": puzzle },
+                    self.scope(doc! { "puzzle.number": puzzle }),
                     doc! { "$addToSet": {
                         "games": game
                     } },
@@ -169,7 +234,10 @@ impl DailyWordles {
             .await?;
 
         while let Some(daily) = cursor.next().await {
-            if daily.as_ref().is_ok_and(|daily| daily.is_expired().not()) {
+            if daily
+                .as_ref()
+                .is_ok_and(|daily| daily.is_expired(&self.schedule).not())
+            {
                 vec.push(daily?);
             }
         }
@@ -178,11 +246,17 @@ impl DailyWordles {
     }
 
     pub async fn playable_for(&self, user: UserId) -> DbResult<impl Iterator<Item = DailyWordle>> {
+        let schedule = self.schedule;
+
         Ok(self
             .not_expired()
             .await?
             .into_iter()
-            .filter(move |daily| daily.is_playable_for(user)))
+            .filter(move |daily| daily.is_playable_for(user, &schedule)))
+    }
+
+    pub async fn find_daily(&self, number: u32) -> DbResult<Option<DailyWordle>> {
+        self.find_one(doc! { "puzzle.number": number }, None).await
     }
 
     pub async fn wordle_exists(&self, number: u32) -> DbResult<bool> {
@@ -197,25 +271,113 @@ impl DailyWordles {
             .await?
             .and_then(|daily| daily.user_game(user).cloned()))
     }
+
+    #[instrument(skip(self))]
+    pub async fn user_stats(&self, user: UserId) -> DbResult<UserStats> {
+        let user = mongodb::bson::ser::to_bson(&user).expect("implements serialize");
+
+        let pipeline = [
+            doc! { "$match": self.scope(doc! { "games.user": &user }) },
+            doc! { "$unwind": "$games" },
+            doc! { "$match": { "games.user": &user, "games.finished": true } },
+            doc! { "$sort": { "puzzle.number": 1 } },
+            doc! { "$project": { "_id": 0, "number": "$puzzle.number", "game": "$games" } },
+        ];
+
+        #[derive(Deserialize)]
+        struct Finished {
+            number: u32,
+            game: GameRecord,
+        }
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await?;
+        let mut stats = UserStats::default();
+
+        while let Some(document) = cursor.try_next().await? {
+            let finished: Finished = mongodb::bson::from_document(document)?;
+            stats.count(finished.number, &finished.game);
+        }
+
+        debug!(?stats);
+
+        Ok(stats)
+    }
+
+    /// Every one of `users` who finished a daily that started within the period,
+    /// ranked from best to worst.
+    #[instrument(skip(self, users))]
+    pub async fn leaderboard(
+        &self,
+        period: LeaderboardPeriod,
+        users: &[UserId],
+    ) -> DbResult<Vec<LeaderboardEntry>> {
+        let users = mongodb::bson::ser::to_bson(users).expect("implements serialize");
+
+        // start times are stored as rfc 3339 strings in utc, which sort the same as the times do
+        let filter = match period.start(Utc::now()) {
+            Some(start) => {
+                let start = mongodb::bson::ser::to_bson(&start).expect("implements serialize");
+                doc! { "puzzle.started": { "$gte": start } }
+            }
+            None => doc! {},
+        };
+
+        let pipeline = [
+            doc! { "$match": self.scope(filter) },
+            doc! { "$unwind": "$games" },
+            doc! { "$match": { "games.finished": true, "games.user": { "$in": users } } },
+            doc! { "$sort": { "puzzle.number": 1 } },
+            doc! { "$group": {
+                "_id": "$games.user",
+                "played": { "$sum": 1 },
+                "solved": { "$sum": { "$cond": ["$games.solved", 1, 0] } },
+                "solved_guesses": {
+                    "$sum": { "$cond": ["$games.solved", "$games.num_guesses", 0] }
+                },
+                "solved_puzzles": {
+                    "$push": { "$cond": ["$games.solved", "$puzzle.number", "$$REMOVE"] }
+                },
+            } },
+        ];
+
+        let mut entries = self
+            .collection
+            .aggregate(pipeline, None)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(mongodb::bson::from_document::<LeaderboardEntry>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        entries.sort_by(LeaderboardEntry::ranking);
+
+        Ok(entries)
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct DailyWordle {
     pub puzzle: puzzle::DailyPuzzle,
     games: Vec<GameRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guild: Option<GuildId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PartialDailyWordle {
     pub puzzle: puzzle::PartialDailyPuzzle,
     games: Vec<GameRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    guild: Option<GuildId>,
 }
 
 impl DailyWordle {
-    fn from_partial(partial: PartialDailyWordle, list: &kwordle::WordsList) -> Option<Self> {
+    fn from_partial(partial: PartialDailyWordle) -> Option<Self> {
         Some(Self {
-            puzzle: puzzle::DailyPuzzle::from_partial(partial.puzzle, list)?,
+            puzzle: puzzle::DailyPuzzle::from_partial(partial.puzzle)?,
             games: partial.games,
+            guild: partial.guild,
         })
     }
 
@@ -223,81 +385,68 @@ impl DailyWordle {
         PartialDailyWordle {
             puzzle: self.puzzle.into_partial(),
             games: self.games,
+            guild: self.guild,
         }
     }
 
-    fn new(puzzle: puzzle::DailyPuzzle) -> Self {
+    fn new(puzzle: puzzle::DailyPuzzle, guild: Option<GuildId>) -> Self {
         Self {
             puzzle,
             games: Vec::new(),
+            guild,
         }
     }
 
-    pub fn age_hours(&self) -> i64 {
-        let age = Utc::now() - self.puzzle.started;
-        age.num_hours()
+    /// How many daily rollovers have happened since this wordle started.
+    pub fn age(&self, schedule: &Schedule) -> i64 {
+        schedule.periods_since(self.puzzle.started, Utc::now())
     }
 
-    pub fn is_recent(&self) -> bool {
-        self.age_hours() < 24
+    pub fn is_recent(&self, schedule: &Schedule) -> bool {
+        self.age(schedule) < 1
     }
 
-    pub fn is_old(&self) -> bool {
-        self.age_hours() < 48 && !self.is_recent()
+    pub fn is_expired(&self, schedule: &Schedule) -> bool {
+        self.age(schedule) >= 2
     }
 
-    pub fn is_expired(&self) -> bool {
-        self.age_hours() >= 48
+    pub fn games(&self) -> &[GameRecord] {
+        &self.games
     }
 
     pub fn user_game(&self, user: UserId) -> Option<&GameRecord> {
         self.games.iter().find(|game| game.user == user)
     }
 
-    #[allow(dead_code)]
-    pub fn played_by(&self, user: UserId) -> bool {
-        self.user_game(user).is_some()
-    }
-
     pub fn finished_by(&self, user: UserId) -> bool {
         self.user_game(user).is_some_and(|game| game.is_finished())
     }
 
-    pub fn is_playable_for(&self, user: UserId) -> bool {
-        self.is_expired().not() && self.finished_by(user).not()
-    }
-
-    #[allow(dead_code)]
-    pub fn in_progress_for(&self, user: UserId) -> bool {
-        self.user_game(user).is_some_and(|game| game.in_progress())
+    pub fn is_playable_for(&self, user: UserId, schedule: &Schedule) -> bool {
+        self.is_expired(schedule).not() && self.finished_by(user).not()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::DailyWordle;
-    use pretty_assertions::assert_str_eq;
+    use super::{daily_command, DailyWordle, Language, PartialDailyWordle};
+    use poise::serenity_prelude::GuildId;
+    use pretty_assertions::{assert_eq, assert_str_eq};
 
     const DAILY_WORDLE_JSON: &str = include_str!("./tests/daily_wordle.json");
 
     #[test]
     fn deserialize() {
-        let words = kwordle::classic::words_list();
-
         DailyWordle::from_partial(
             serde_json::from_str(DAILY_WORDLE_JSON).expect("should be valid json"),
-            &words,
         )
         .expect("should be valid DailyWordle");
     }
 
     #[test]
     fn serialize_stable() {
-        let words = kwordle::classic::words_list();
-
         let daily_wordle = DailyWordle::from_partial(
             serde_json::from_str(DAILY_WORDLE_JSON).expect("should be valid json"),
-            &words,
         )
         .expect("should be valid DailyWordle");
 
@@ -307,14 +456,41 @@ mod tests {
         assert_str_eq!(serialized, DAILY_WORDLE_JSON)
     }
 
+    #[test]
+    fn answer_not_in_words_list() {
+        let json = DAILY_WORDLE_JSON.replace("\"onion\"", "\"xyzzy\"");
+
+        let daily_wordle =
+            DailyWordle::from_partial(serde_json::from_str(&json).expect("should be valid json"))
+                .expect("should be valid DailyWordle");
+
+        assert_eq!(daily_wordle.puzzle.answer().to_string(), "xyzzy");
+    }
+
+    #[test]
+    fn guild_round_trip() {
+        let mut daily_wordle = DailyWordle::from_partial(
+            serde_json::from_str(DAILY_WORDLE_JSON).expect("should be valid json"),
+        )
+        .expect("should be valid DailyWordle");
+
+        assert_eq!(daily_wordle.guild, None);
+
+        daily_wordle.guild = Some(GuildId::new(1234));
+
+        let serialized =
+            serde_json::to_string(&daily_wordle.into_partial()).expect("should serialize properly");
+        let partial: PartialDailyWordle =
+            serde_json::from_str(&serialized).expect("should be valid json");
+
+        assert_eq!(partial.guild, Some(GuildId::new(1234)));
+    }
+
     #[test]
     #[should_panic]
     fn serialize_fail() {
-        let words = kwordle::classic::words_list();
-
         let mut daily_wordle = DailyWordle::from_partial(
             serde_json::from_str(DAILY_WORDLE_JSON).expect("should be valid json"),
-            &words,
         )
         .expect("should be valid DailyWordle");
 
@@ -325,4 +501,17 @@ mod tests {
 
         assert_str_eq!(serialized, DAILY_WORDLE_JSON)
     }
+
+    #[test]
+    fn paused_daily_command() {
+        assert_eq!(daily_command(Language::English, None), "`/wordle daily`");
+        assert_eq!(
+            daily_command(Language::English, Some(GuildId::new(1))),
+            "`/wordle daily` in the server it's from"
+        );
+        assert_eq!(
+            daily_command(Language::Spanish, None),
+            "`/wordle daily language:español`"
+        );
+    }
 }
@@ -1,36 +1,425 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use chrono::Utc;
 use mongodb::Database;
+use poise::serenity_prelude::{
+    CacheHttp, ChannelId, CreateAllowedMentions, CreateMessage, GuildId, Http, Mentionable,
+    MessageId, RoleId, User,
+};
+use poise::ChoiceParameter;
+use tracing::{error, info, warn};
+
+use crate::{errors::CommandError, framework::config::WordleConfig};
+
+use super::{
+    game::{self, GamesCache, GuessError, Submitter},
+    hint::HintKind,
+    words_list, ActiveGames, ArchivedGames, Challenges, DailyWordle, DailyWordles, DbResult,
+    Language, LanguageData, Schedule, Speedruns,
+};
 
-use super::{game::GamesCache, DailyWordles};
+/// Calls a [`WordLength`] function with each word length in turn,
+/// giving the first thing it finds.
+macro_rules! find_in_lengths {
+    ($data:expr, $($function:ident)::+($($arg:expr),* $(,)?)) => {{
+        let data = $data;
+
+        if let Some(found) = $($function)::+(Some(&data.classic), $($arg),*).await {
+            Some(found)
+        } else if let Some(found) = $($function)::+(data.four.as_ref(), $($arg),*).await {
+            Some(found)
+        } else if let Some(found) = $($function)::+(data.six.as_ref(), $($arg),*).await {
+            Some(found)
+        } else {
+            $($function)::+(data.seven.as_ref(), $($arg),*).await
+        }
+    }};
+}
 
 #[derive(Debug, Clone)]
 pub struct WordleData {
-    words: kwordle::WordsList<5>,
+    classic: WordLength<5>,
+    four: Option<WordLength<4>>,
+    six: Option<WordLength<6>>,
+    seven: Option<WordLength<7>>,
     wordles: DailyWordles,
-    game_data: GamesCache,
+    /// Dailies for servers that have their own.
+    guilds: Vec<DailyWordles>,
+    languages: Vec<LanguageData>,
+    active_games: ActiveGames,
+    archive: ArchivedGames,
+    challenges: Challenges,
+    speedruns: Speedruns,
+    rollover_started: Arc<AtomicBool>,
 }
 
 impl WordleData {
-    pub fn new(db: &Database) -> Self {
-        let words = kwordle::classic::words_list();
-        let wordles = DailyWordles::new(db, &words);
-        let game_data = GamesCache::new();
+    pub fn new(db: &Database, config: &WordleConfig) -> Self {
+        for list in &config.extra_lists {
+            if ![4, 6, 7].contains(&list.length) {
+                warn!(
+                    length = list.length,
+                    "only 4, 6 and 7-letter extra words lists are supported, ignoring"
+                );
+            }
+        }
+
+        let schedule = Schedule::from_config(config);
+        let classic = kwordle::classic::words_list();
+
+        let words = match (&config.guesses_file, &config.answers_file) {
+            (Some(guesses_file), Some(answers_file)) => {
+                match words_list::load(guesses_file, answers_file) {
+                    Ok(words) => words,
+                    Err(err) => {
+                        error!("failed to load custom words list, using the classic list: {err}");
+                        classic
+                    }
+                }
+            }
+            (None, None) => classic,
+            _ => {
+                error!("wordle.guesses_file and wordle.answers_file must be set together, using the classic list");
+                classic
+            }
+        };
+
+        // dailies from before the list changed are read whether or not their answers are still in it
+        let wordles = DailyWordles::new(db, schedule);
+
+        let languages = config
+            .languages
+            .iter()
+            .filter_map(|files| {
+                let Some(language) = Language::from_name(&files.language) else {
+                    warn!(
+                        language = %files.language,
+                        "unknown language in wordle.languages, ignoring it"
+                    );
+                    return None;
+                };
+
+                if language.is_english() {
+                    warn!("english is the usual words list, ignoring it in wordle.languages");
+                    return None;
+                }
+
+                LanguageData::load(db, language, files, schedule)
+                    .map_err(|err| {
+                        error!(
+                            language = language.name(),
+                            "failed to load words list: {err}"
+                        );
+                    })
+                    .ok()
+            })
+            .collect();
+
+        let guilds = config
+            .guilds
+            .iter()
+            .map(|guild| wordles.in_guild(guild.guild_id))
+            .collect();
 
         Self {
-            words,
+            classic: WordLength::new(words),
+            four: WordLength::from_config(config),
+            six: WordLength::from_config(config),
+            seven: WordLength::from_config(config),
             wordles,
-            game_data,
+            guilds,
+            languages,
+            active_games: ActiveGames::new(db),
+            archive: ArchivedGames::new(db),
+            challenges: Challenges::new(db),
+            speedruns: Speedruns::new(db),
+            rollover_started: Arc::default(),
         }
     }
 
     pub const fn words(&self) -> &kwordle::WordsList<5> {
-        &self.words
+        self.classic.words()
+    }
+
+    /// The dailies played from a server, which are its own if it has them.
+    /// Outside of servers, like in DMs, it's the dailies every server shares.
+    pub fn wordles_in(&self, guild: Option<GuildId>) -> &DailyWordles {
+        guild
+            .and_then(|guild| {
+                self.guilds
+                    .iter()
+                    .find(|wordles| wordles.guild() == Some(guild))
+            })
+            .unwrap_or(&self.wordles)
     }
 
-    pub const fn wordles(&self) -> &DailyWordles {
-        &self.wordles
+    /// The words and dailies for a language, if it's set up.
+    pub fn language(&self, language: Language) -> Option<&LanguageData> {
+        self.languages
+            .iter()
+            .find(|data| data.language() == language)
     }
 
     pub const fn game_data(&self) -> &GamesCache {
+        self.classic.game_data()
+    }
+
+    pub const fn active_games(&self) -> &ActiveGames {
+        &self.active_games
+    }
+
+    pub const fn archive(&self) -> &ArchivedGames {
+        &self.archive
+    }
+
+    pub const fn challenges(&self) -> &Challenges {
+        &self.challenges
+    }
+
+    pub const fn speedruns(&self) -> &Speedruns {
+        &self.speedruns
+    }
+
+    /// Starts new daily wordles if they're due, and announces them in the wordle channels.
+    /// Gives the new daily every server shares, if there is one.
+    pub async fn rollover(
+        &self,
+        cache_http: impl CacheHttp,
+        config: &WordleConfig,
+    ) -> Result<Option<DailyWordle>, CommandError> {
+        for language in &self.languages {
+            if let Some(daily) = language.wordles().refresh(language.words()).await? {
+                info!(
+                    language = language.language().name(),
+                    number = daily.puzzle.number,
+                    "new daily wordle"
+                );
+            }
+        }
+
+        for wordles in &self.guilds {
+            let guild = config.guild(wordles.guild());
+
+            // one server's channel going missing shouldn't stop the rest
+            if let Err(err) = self
+                .announce_daily(
+                    wordles,
+                    &cache_http,
+                    guild.and_then(|guild| guild.channel_id),
+                    guild.and_then(|guild| guild.role_id),
+                )
+                .await
+            {
+                error!(guild = ?wordles.guild(), "failed to roll over daily wordle: {err}");
+            }
+        }
+
+        self.announce_daily(
+            &self.wordles,
+            &cache_http,
+            config.channel_id,
+            config.role_id,
+        )
+        .await
+    }
+
+    /// Starts a new daily if it's due, then announces it and the results of the one that just expired.
+    async fn announce_daily(
+        &self,
+        wordles: &DailyWordles,
+        cache_http: impl CacheHttp,
+        channel: Option<ChannelId>,
+        role: Option<RoleId>,
+    ) -> Result<Option<DailyWordle>, CommandError> {
+        let Some(new_daily) = wordles.refresh(self.words()).await? else {
+            return Ok(None);
+        };
+
+        info!(
+            guild = ?wordles.guild(),
+            number = new_daily.puzzle.number,
+            "new daily wordle"
+        );
+
+        if let Some(channel) = channel
+            && let Some(role) = role
+        {
+            channel
+                .say(
+                    &cache_http,
+                    format!(
+                        "{ping} **Daily wordle {number} now available!**\nPlay it with `/wordle daily`",
+                        ping = role.mention(),
+                        number = new_daily.puzzle.number
+                    ),
+                )
+                .await?;
+        }
+
+        // the daily from two rollovers ago just expired, so its results can be shared
+        if let Some(channel) = channel
+            && let Some(number) = new_daily.puzzle.number.checked_sub(2)
+            && let Some(expired) = wordles.find_daily(number).await?
+        {
+            for message in super::digest(&expired) {
+                channel
+                    .send_message(
+                        &cache_http,
+                        CreateMessage::new()
+                            .content(message)
+                            .allowed_mentions(CreateAllowedMentions::new()),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(Some(new_daily))
+    }
+
+    /// Runs [`Self::rollover`] now and then at every scheduled rollover.
+    /// Only the first call starts the task, so reconnecting doesn't start it again.
+    #[tracing::instrument(skip_all)]
+    pub fn rollover_task(self, http: Arc<Http>, config: WordleConfig) {
+        if self.rollover_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = self.rollover(http.as_ref(), &config).await {
+                    error!("failed to roll over daily wordle: {err}");
+                }
+
+                let now = Utc::now();
+                let wait = (self.wordles.schedule().next_rollover(now) - now)
+                    .to_std()
+                    .unwrap_or_default();
+
+                // a little extra, so the new period has definitely started
+                tokio::time::sleep(wait + std::time::Duration::from_secs(1)).await;
+            }
+        });
+    }
+
+    /// Gives a hint for the channel's game, whatever its word length.
+    pub async fn hint(&self, channel: ChannelId, kind: HintKind) -> Option<String> {
+        find_in_lengths!(self, WordLength::hint(channel, kind))
+    }
+
+    /// Plays a guess in the channel's game, whatever its word length.
+    /// Gives nothing if there isn't a game to play it in.
+    pub async fn guess(
+        &self,
+        channel: ChannelId,
+        user: User,
+        content: String,
+        sent: i64,
+    ) -> Option<Result<(), GuessError>> {
+        let submitter = find_in_lengths!(self, WordLength::submitter(channel))?;
+
+        Some(game::submit(&submitter, user, content, sent).await)
+    }
+
+    /// Finds the message of a game being played in the channel, whatever its word length.
+    pub async fn active_game(&self, channel: ChannelId) -> Option<MessageId> {
+        find_in_lengths!(self, WordLength::active_game(channel))
+    }
+
+    /// Letters that haven't been guessed yet in the channel's game, whatever its word length.
+    pub async fn unused_letters(&self, channel: ChannelId) -> Option<kwordle::letter::LetterSet> {
+        find_in_lengths!(self, WordLength::unused_letters(channel))
+    }
+}
+
+/// The words list and running games for one word length.
+#[derive(Debug, Clone)]
+pub struct WordLength<const N: usize> {
+    words: kwordle::WordsList<N>,
+    game_data: GamesCache<N>,
+}
+
+impl<const N: usize> WordLength<N> {
+    pub fn new(words: kwordle::WordsList<N>) -> Self {
+        Self {
+            words,
+            game_data: GamesCache::new(),
+        }
+    }
+
+    fn from_config(config: &WordleConfig) -> Option<Self> {
+        let files = config.extra_lists.iter().find(|list| list.length == N)?;
+
+        match words_list::load(&files.guesses_file, &files.answers_file) {
+            Ok(words) => Some(Self::new(words)),
+            Err(err) => {
+                error!(length = N, "failed to load extra words list: {err}");
+                None
+            }
+        }
+    }
+
+    pub const fn words(&self) -> &kwordle::WordsList<N> {
+        &self.words
+    }
+
+    pub const fn game_data(&self) -> &GamesCache<N> {
         &self.game_data
     }
+
+    async fn active_game(length: Option<&Self>, channel: ChannelId) -> Option<MessageId> {
+        let data = length?.game_data().get(channel).await?;
+        Some(data.message_id)
+    }
+
+    async fn unused_letters(
+        length: Option<&Self>,
+        channel: ChannelId,
+    ) -> Option<kwordle::letter::LetterSet> {
+        let data = length?.game_data().get(channel).await?;
+        Some(data.guesses.unused_letters())
+    }
+
+    async fn submitter(length: Option<&Self>, channel: ChannelId) -> Option<Submitter> {
+        let data = length?.game_data().get(channel).await?;
+        data.submitter.clone()
+    }
+
+    async fn hint(length: Option<&Self>, channel: ChannelId, kind: HintKind) -> Option<String> {
+        let length = length?;
+        let data = length.game_data().get(channel).await?;
+        let words = data.words.as_deref().unwrap_or(length.words());
+        Some(super::hint::hint(kind, &data, words).await)
+    }
+}
+
+/// Gives access to the words list and games for words of length `N`, if that length is available.
+pub trait HasLength<const N: usize> {
+    fn length(&self) -> Option<&WordLength<N>>;
+}
+
+impl HasLength<4> for WordleData {
+    fn length(&self) -> Option<&WordLength<4>> {
+        self.four.as_ref()
+    }
+}
+
+impl HasLength<5> for WordleData {
+    fn length(&self) -> Option<&WordLength<5>> {
+        Some(&self.classic)
+    }
+}
+
+impl HasLength<6> for WordleData {
+    fn length(&self) -> Option<&WordLength<6>> {
+        self.six.as_ref()
+    }
+}
+
+impl HasLength<7> for WordleData {
+    fn length(&self) -> Option<&WordLength<7>> {
+        self.seven.as_ref()
+    }
 }
@@ -0,0 +1,131 @@
+use poise::serenity_prelude::Mentionable;
+
+use super::{AsEmoji, DailyWordle, GameRecord};
+
+/// Discord's limit on the length of a message.
+const MESSAGE_LIMIT: usize = 2000;
+
+fn result(game: &GameRecord) -> String {
+    let user = game.user.mention();
+
+    if game.is_solved() {
+        let guesses = game.num_guesses;
+        let plural = if guesses == 1 { "" } else { "es" };
+        format!(
+            "{user} solved it in {guesses} guess{plural}\n||{}||",
+            game.as_emoji()
+        )
+    } else if game.gave_up() {
+        format!("{user} gave up\n||{}||", game.as_emoji())
+    } else if game.is_finished() {
+        format!("{user} didn't solve it\n||{}||", game.as_emoji())
+    } else {
+        format!("{user} didn't finish\n||{}||", game.as_emoji())
+    }
+}
+
+/// Sorts games with the best results first: solved in the fewest guesses,
+/// then the ones that ran out of guesses or gave up, then the ones that didn't finish.
+fn ranked(games: &[GameRecord]) -> Vec<&GameRecord> {
+    let mut games: Vec<&GameRecord> = games.iter().filter(|game| game.num_guesses > 0).collect();
+
+    games.sort_by_key(|game| {
+        let rank = if game.is_solved() {
+            0
+        } else if game.is_finished() {
+            1
+        } else {
+            2
+        };
+
+        (rank, game.num_guesses)
+    });
+
+    games
+}
+
+/// Joins lines into as few messages as possible, without going over the length limit.
+fn into_messages(lines: impl IntoIterator<Item = String>, limit: usize) -> Vec<String> {
+    let mut messages: Vec<String> = Vec::new();
+
+    for line in lines {
+        match messages.last_mut() {
+            Some(message) if message.chars().count() + 1 + line.chars().count() <= limit => {
+                message.push('\n');
+                message.push_str(&line);
+            }
+            _ => messages.push(line),
+        }
+    }
+
+    messages
+}
+
+/// The results of everyone who played a daily wordle, to post once it's expired.
+/// Returns nothing if nobody played it.
+pub fn digest(daily: &DailyWordle) -> Vec<String> {
+    let games = ranked(daily.games());
+
+    if games.is_empty() {
+        return Vec::new();
+    }
+
+    let header = format!(
+        "**daily wordle {number} is over!** the word was `{answer}`",
+        number = daily.puzzle.number,
+        answer = daily.puzzle.answer()
+    );
+
+    into_messages(
+        std::iter::once(header).chain(games.into_iter().map(result)),
+        MESSAGE_LIMIT,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{into_messages, ranked, result};
+    use crate::commands::wordle::core::GameRecord;
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    const DAILY_WORDLE_JSON: &str = include_str!("./tests/daily_wordle.json");
+
+    fn games() -> Vec<GameRecord> {
+        let daily: serde_json::Value =
+            serde_json::from_str(DAILY_WORDLE_JSON).expect("should be valid json");
+
+        serde_json::from_value(daily["games"].clone()).expect("should be valid games")
+    }
+
+    #[test]
+    fn messages() {
+        let lines = ["aaaa", "bbbb", "cccc", "dd"].map(str::to_owned);
+
+        assert_eq!(into_messages(lines, 10), vec!["aaaa\nbbbb", "cccc\ndd"]);
+    }
+
+    #[test]
+    fn results() {
+        let games = games();
+        let ranked = ranked(&games);
+
+        assert_eq!(ranked.len(), 4);
+        assert!(result(ranked[0]).starts_with("<@1> solved it in 5 guesses\n||"));
+    }
+
+    #[test]
+    fn unsolved() {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let mut guesses = kwordle::Guesses::new(Some(6));
+        guesses.push(answer.guess(kwordle::Word::from_str(&words, "chair").unwrap()));
+
+        let lost = GameRecord::new(UserId::new(1), guesses, true, false);
+        assert!(result(&lost).starts_with("<@1> didn't solve it\n||"));
+
+        let gave_up = lost.with_gave_up(true);
+        assert!(result(&gave_up).starts_with("<@1> gave up\n||"));
+    }
+}
@@ -0,0 +1,114 @@
+use super::super::AsEmoji;
+use super::options::GameStyle;
+
+/// Shown in place of a row on a board that was already solved.
+const EMPTY: &str = "⬜";
+
+/// One stream of guesses played against several answers at once.
+/// Each board stops taking guesses once its own answer is found.
+#[derive(Debug, Clone)]
+pub struct Boards<const N: usize = 5> {
+    words: Vec<kwordle::Word<N>>,
+    boards: Vec<(kwordle::Word<N>, kwordle::Guesses<N>)>,
+}
+
+impl<const N: usize> Boards<N> {
+    pub fn new(answers: &[kwordle::Word<N>]) -> Self {
+        Self {
+            words: Vec::new(),
+            boards: answers
+                .iter()
+                .map(|answer| (*answer, kwordle::Guesses::new(None)))
+                .collect(),
+        }
+    }
+
+    pub fn guess(&mut self, word: kwordle::Word<N>) {
+        self.words.push(word);
+
+        for (answer, guesses) in &mut self.boards {
+            if !guesses.latest_is_correct() {
+                guesses.push(answer.guess(word));
+            }
+        }
+    }
+
+    pub fn solved(&self) -> usize {
+        self.boards
+            .iter()
+            .filter(|(_, guesses)| guesses.latest_is_correct())
+            .count()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.solved() == self.boards.len()
+    }
+
+    /// Every board side by side, one row per guess.
+    /// Unless only colors are wanted, each row ends with the word that was guessed.
+    pub fn render(&self, style: GameStyle) -> String {
+        self.words
+            .iter()
+            .enumerate()
+            .map(|(row, word)| {
+                let boards: Vec<String> = self
+                    .boards
+                    .iter()
+                    .map(|(_, guesses)| {
+                        guesses
+                            .iter()
+                            .nth(row)
+                            .map_or_else(|| EMPTY.repeat(N), |guess| guess.as_emoji().into_owned())
+                    })
+                    .collect();
+
+                match style {
+                    GameStyle::Colors | GameStyle::Image => boards.join("  "),
+                    GameStyle::Letters | GameStyle::SpacedLetters => {
+                        format!("{boards} `{word}`", boards = boards.join("  "))
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boards, GameStyle};
+    use pretty_assertions::assert_eq;
+
+    fn word(word: &str) -> kwordle::Word<5> {
+        kwordle::Word::from_str(&kwordle::classic::words_list(), word).expect("should be valid")
+    }
+
+    #[test]
+    fn solved_independently() {
+        let mut boards = Boards::new(&[word("onion"), word("chair")]);
+
+        boards.guess(word("onion"));
+        assert_eq!(boards.solved(), 1);
+        assert!(!boards.is_solved());
+
+        boards.guess(word("chair"));
+        assert!(boards.is_solved());
+    }
+
+    #[test]
+    fn render() {
+        let mut boards = Boards::new(&[word("onion"), word("chair")]);
+
+        boards.guess(word("onion"));
+        boards.guess(word("chair"));
+
+        assert_eq!(
+            boards.render(GameStyle::Letters),
+            "🟩🟩🟩🟩🟩  ⬛⬛🟨⬛⬛ `onion`\n⬜⬜⬜⬜⬜  🟩🟩🟩🟩🟩 `chair`"
+        );
+        assert_eq!(
+            boards.render(GameStyle::Colors).lines().last(),
+            Some("⬜⬜⬜⬜⬜  🟩🟩🟩🟩🟩")
+        );
+    }
+}
@@ -8,14 +8,16 @@ use tokio::sync::RwLock;
 use super::GameData;
 
 #[derive(Clone, Debug, Default)]
-pub struct GamesCache(Arc<RwLock<HashMap<ChannelId, Arc<ArcSwap<GameData>>>>>);
+pub struct GamesCache<const N: usize = 5>(
+    Arc<RwLock<HashMap<ChannelId, Arc<ArcSwap<GameData<N>>>>>>,
+);
 
-impl GamesCache {
+impl<const N: usize> GamesCache<N> {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub async fn get(&self, channel_id: ChannelId) -> Option<Arc<GameData>> {
+    pub async fn get(&self, channel_id: ChannelId) -> Option<Arc<GameData<N>>> {
         let guard = self.0.read().await;
         guard.get(&channel_id).map(|arc_swap| arc_swap.load_full())
     }
@@ -24,7 +26,7 @@ impl GamesCache {
         self.get(id).await.is_some()
     }
 
-    pub async fn set(&self, channel_id: ChannelId, new_data: GameData) -> Arc<GameData> {
+    pub async fn set(&self, channel_id: ChannelId, new_data: GameData<N>) -> Arc<GameData<N>> {
         let arc = Arc::new(new_data);
         let mut guard = self.0.write().await;
         if let Some(arc_swap) = guard.get_mut(&channel_id) {
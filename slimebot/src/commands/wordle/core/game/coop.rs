@@ -0,0 +1,98 @@
+use std::fmt::Write;
+
+use poise::serenity_prelude::{Mention, UserId};
+
+/// Whether anyone besides the game's owner made guesses.
+pub fn is_coop(owner: UserId, authors: &[UserId]) -> bool {
+    authors.iter().any(|author| *author != owner)
+}
+
+/// A row of the board, followed by who guessed it.
+pub fn label(row: &str, author: UserId) -> String {
+    format!("{row} {mention}", mention = Mention::User(author))
+}
+
+/// How many guesses each person made, most first.
+/// Ties keep the order people first guessed in.
+pub fn contributions(authors: &[UserId]) -> Vec<(UserId, usize)> {
+    let mut counts: Vec<(UserId, usize)> = Vec::new();
+
+    for author in authors {
+        match counts.iter_mut().find(|(user, _)| user == author) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*author, 1)),
+        }
+    }
+
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+/// Who found the word, and what everyone else added.
+pub fn summary(authors: &[UserId], solved: bool) -> String {
+    let mut summary = "**co-op summary**".to_owned();
+
+    if solved && let Some(winner) = authors.last() {
+        write!(summary, "\nwinning guess: {}", Mention::User(*winner))
+            .expect("writing to a string");
+    }
+
+    for (user, count) in contributions(authors) {
+        let plural = if count == 1 { "" } else { "es" };
+
+        write!(summary, "\n{}: {count} guess{plural}", Mention::User(user))
+            .expect("writing to a string");
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contributions, is_coop, summary};
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    fn users(ids: &[u64]) -> Vec<UserId> {
+        ids.iter().copied().map(UserId::new).collect()
+    }
+
+    #[test]
+    fn coop() {
+        let owner = UserId::new(1);
+
+        assert!(!is_coop(owner, &[]));
+        assert!(!is_coop(owner, &users(&[1, 1, 1])));
+        assert!(is_coop(owner, &users(&[1, 2, 1])));
+        // someone else made every guess in the owner's game
+        assert!(is_coop(owner, &users(&[2, 2])));
+    }
+
+    #[test]
+    fn contributions_order() {
+        assert_eq!(
+            contributions(&users(&[1, 2, 3, 2, 3])),
+            vec![
+                (UserId::new(2), 2),
+                (UserId::new(3), 2),
+                (UserId::new(1), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_solved() {
+        assert_eq!(
+            summary(&users(&[1, 2, 1]), true),
+            "**co-op summary**\nwinning guess: <@1>\n<@1>: 2 guesses\n<@2>: 1 guess"
+        );
+    }
+
+    #[test]
+    fn summary_unsolved() {
+        assert_eq!(
+            summary(&users(&[1, 2]), false),
+            "**co-op summary**\n<@1>: 1 guess\n<@2>: 1 guess"
+        );
+    }
+}
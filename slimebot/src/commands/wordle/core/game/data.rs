@@ -1,11 +1,30 @@
-use std::sync::Arc;
+use std::sync::{atomic::AtomicUsize, Arc};
 
-use super::Puzzle;
-use poise::serenity_prelude::MessageId;
+use super::{
+    super::{Language, Timer},
+    Boards, Puzzle, Submitter,
+};
+use poise::serenity_prelude::{MessageId, UserId};
 
 #[derive(Clone, Debug)]
-pub struct GameData {
-    pub puzzle: Arc<Puzzle>,
-    pub guesses: kwordle::Guesses,
+pub struct GameData<const N: usize = 5> {
+    /// Whoever started the game.
+    pub owner: UserId,
+    pub puzzle: Arc<Puzzle<N>>,
+    pub guesses: kwordle::Guesses<N>,
     pub message_id: MessageId,
+    pub hard_mode: bool,
+    /// Shared with the running game, so hints given outside of it are counted.
+    pub hints: Arc<AtomicUsize>,
+    /// Who made each guess.
+    pub authors: Vec<UserId>,
+    /// Every board, for puzzles with more than one answer.
+    pub boards: Option<Boards<N>>,
+    /// Takes guesses sent from outside the game, once it's running.
+    pub submitter: Option<Submitter>,
+    pub language: Language,
+    /// The words list, if it isn't the usual one for the word length.
+    pub words: Option<Arc<kwordle::WordsList<N>>>,
+    /// Only for timed games.
+    pub timer: Option<Timer>,
 }
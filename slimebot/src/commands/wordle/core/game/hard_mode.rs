@@ -0,0 +1,144 @@
+use std::{borrow::Borrow, collections::BTreeMap, fmt};
+
+/// A hard mode rule broken by a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// A letter revealed in the right place was moved or dropped.
+    Correct { letter: char, position: usize },
+    /// A letter revealed to be in the word was left out.
+    Present { letter: char },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct { letter, position } => write!(
+                f,
+                "hard mode: letter {position} must be `{letter}`",
+                letter = letter.to_ascii_uppercase(),
+                position = position + 1
+            ),
+            Self::Present { letter } => write!(
+                f,
+                "hard mode: guess must contain `{letter}`",
+                letter = letter.to_ascii_uppercase()
+            ),
+        }
+    }
+}
+
+pub(crate) fn letter_char(letter: impl Borrow<kwordle::Letter>) -> char {
+    kwordle::letter::ALPHABET
+        .zip('a'..='z')
+        .find_map(|(alphabet_letter, c)| (*letter.borrow() == alphabet_letter).then_some(c))
+        .expect("letter should be alphabetic")
+}
+
+/// Checks a new guess against everything revealed by the previous ones.
+///
+/// Green letters have to stay in place and yellow letters have to be reused,
+/// as many times as they were revealed. Green letters are checked first.
+pub fn check<const N: usize>(
+    guesses: &kwordle::Guesses<N>,
+    word: &kwordle::Word<N>,
+) -> Option<Violation> {
+    let word: Vec<char> = word.to_string().chars().collect();
+
+    for guess in guesses.iter() {
+        for (position, (letter, state)) in guess.into_iter().enumerate() {
+            let letter = letter_char(letter);
+
+            if matches!(state, kwordle::LetterState::Correct) && word.get(position) != Some(&letter)
+            {
+                return Some(Violation::Correct { letter, position });
+            }
+        }
+    }
+
+    for guess in guesses.iter() {
+        let mut revealed: BTreeMap<char, usize> = BTreeMap::new();
+
+        for (letter, state) in guess {
+            if !matches!(state, kwordle::LetterState::NotPresent) {
+                *revealed.entry(letter_char(letter)).or_default() += 1;
+            }
+        }
+
+        for (letter, count) in revealed {
+            if word.iter().filter(|&&c| c == letter).count() < count {
+                return Some(Violation::Present { letter });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, Violation};
+    use pretty_assertions::assert_eq;
+
+    fn guesses(answer: &str, words: &[&str]) -> kwordle::Guesses<5> {
+        let list = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&list, answer).expect("answer should be valid");
+
+        let mut guesses = kwordle::Guesses::new(None);
+
+        for word in words {
+            let word = kwordle::Word::from_str(&list, word).expect("guess should be valid");
+            guesses.push(answer.guess(word));
+        }
+
+        guesses
+    }
+
+    fn word(word: &str) -> kwordle::Word<5> {
+        kwordle::Word::from_str(&kwordle::classic::words_list(), word).expect("should be valid")
+    }
+
+    #[test]
+    fn no_guesses() {
+        assert_eq!(check(&guesses("onion", &[]), &word("crane")), None);
+    }
+
+    #[test]
+    fn present_letter_reused() {
+        assert_eq!(check(&guesses("onion", &["chair"]), &word("point")), None);
+    }
+
+    #[test]
+    fn present_letter_missing() {
+        assert_eq!(
+            check(&guesses("onion", &["chair"]), &word("crane")),
+            Some(Violation::Present { letter: 'i' })
+        );
+    }
+
+    #[test]
+    fn correct_letter_kept() {
+        assert_eq!(
+            check(&guesses("onion", &["chair", "point"]), &word("noisy")),
+            None
+        );
+    }
+
+    #[test]
+    fn correct_letter_moved() {
+        assert_eq!(
+            check(&guesses("onion", &["chair", "point"]), &word("ionic")),
+            Some(Violation::Correct {
+                letter: 'i',
+                position: 2
+            })
+        );
+    }
+
+    #[test]
+    fn second_present_letter_missing() {
+        assert_eq!(
+            check(&guesses("onion", &["chair", "point"]), &word("moist")),
+            Some(Violation::Present { letter: 'n' })
+        );
+    }
+}
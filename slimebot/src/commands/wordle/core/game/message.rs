@@ -1,14 +1,14 @@
 use poise::serenity_prelude::{
     self, futures::Stream, CacheHttp, ChannelId, ComponentInteraction, CreateActionRow,
-    CreateButton, CreateMessage, EditMessage, Message, MessageId, ReactionType, Result,
-    ShardMessenger,
+    CreateAttachment, CreateButton, CreateMessage, EditAttachments, EditMessage, Message,
+    MessageId, ModalInteraction, ModalInteractionCollector, ReactionType, Result, ShardMessenger,
 };
 
 use crate::utils::{poise::ContextExt, Context};
 
-use super::{options::GameStyle, GameContext, GameData};
+use super::{coop, options::GameStyle, GameContext, GameData};
 
-use super::super::{AsEmoji, Puzzle};
+use super::super::{render, AsEmoji, Puzzle};
 
 pub struct GameMessage {
     msg: Message,
@@ -38,6 +38,22 @@ impl GameMessage {
             .await
     }
 
+    /// Adds a message to the end of the game and removes its buttons.
+    pub async fn close(
+        &mut self,
+        cache_http: impl CacheHttp,
+        message: impl AsRef<str>,
+    ) -> Result<()> {
+        self.msg
+            .edit(
+                cache_http,
+                EditMessage::new()
+                    .content(format!("{}\n{}", self.msg.content, message.as_ref()))
+                    .components(Vec::new()),
+            )
+            .await
+    }
+
     pub fn channel_id(&self) -> &ChannelId {
         &self.msg.channel_id
     }
@@ -57,39 +73,126 @@ impl GameMessage {
         self.msg.await_component_interactions(shard).stream()
     }
 
-    fn content(data: &impl AsRef<GameData>, style: GameStyle) -> String {
+    /// The id of the guess popup for this game, so it isn't mixed up with other games'.
+    pub fn modal_id(&self) -> String {
+        format!("guess_{}", self.msg.id)
+    }
+
+    pub fn modals_stream(
+        &self,
+        shard: impl AsRef<ShardMessenger>,
+    ) -> impl Stream<Item = ModalInteraction> {
+        let modal_id = self.modal_id();
+
+        ModalInteractionCollector::new(shard)
+            .filter(move |interaction| interaction.data.custom_id == modal_id)
+            .stream()
+    }
+
+    fn content<const N: usize>(data: &impl AsRef<GameData<N>>, style: GameStyle) -> String {
         let data = data.as_ref();
 
         format!(
-            "{title} {guesses}/{max}\n{emojis}",
+            "{title}{language}{hard_mode} {guesses}/{max}{solved}\n{timer}{emojis}",
             title = data.puzzle.title(),
+            language = if data.language.is_english() {
+                String::new()
+            } else {
+                format!(" ({})", data.language.name())
+            },
+            hard_mode = if data.hard_mode { " (hard mode)" } else { "" },
+            solved = data.boards.as_ref().map_or(String::new(), |boards| format!(
+                " ({solved}/{total} solved)",
+                solved = boards.solved(),
+                total = data.puzzle.answers().len()
+            )),
             guesses = data.guesses.count(),
             max = data
                 .guesses
                 .max()
                 .map_or("∞".to_owned(), |lim| lim.to_string()),
-            emojis = data.guesses.emoji_with_style(style)
+            timer = data
+                .timer
+                .map_or(String::new(), |timer| format!("{timer}\n")),
+            emojis = Self::board(data, style)
         )
     }
 
-    fn builder(data: impl AsRef<GameData>, style: GameStyle) -> EditMessage {
-        EditMessage::new()
-            .content(Self::content(&data, style))
-            .components(Self::buttons(&data))
+    /// The guesses, with who made each one if more than one person is playing.
+    fn board<const N: usize>(data: &GameData<N>, style: GameStyle) -> String {
+        if Self::draws_image(data, style) {
+            return String::new();
+        }
+
+        if let Some(boards) = &data.boards {
+            return boards.render(style);
+        }
+
+        if !coop::is_coop(data.owner, &data.authors) {
+            return data.guesses.emoji_with_style(style).into_owned();
+        }
+
+        data.guesses
+            .iter()
+            .zip(&data.authors)
+            .map(|(guess, author)| coop::label(&guess.emoji_with_style(style), *author))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    pub async fn new(ctx: Context<'_>, puzzle: &Puzzle, style: GameStyle) -> Result<Self> {
+    /// Whether the board is drawn as a picture.
+    /// Boards with more to show than one picture can are still written out.
+    fn draws_image<const N: usize>(data: &GameData<N>, style: GameStyle) -> bool {
+        matches!(style, GameStyle::Image)
+            && data.boards.is_none()
+            && !coop::is_coop(data.owner, &data.authors)
+    }
+
+    fn builder<const N: usize>(data: impl AsRef<GameData<N>>, style: GameStyle) -> EditMessage {
+        let builder = EditMessage::new()
+            .content(Self::content::<N>(&data, style))
+            .components(Self::buttons::<N>(&data));
+
+        if Self::draws_image(data.as_ref(), style) {
+            let png = render::board(&data.as_ref().guesses);
+            builder
+                .attachments(EditAttachments::new().add(CreateAttachment::bytes(png, "wordle.png")))
+        } else {
+            builder
+        }
+    }
+
+    pub async fn new<const N: usize>(
+        ctx: Context<'_>,
+        puzzle: &Puzzle<N>,
+        style: GameStyle,
+    ) -> Result<Self> {
         Ok(Self {
             msg: Self::loading_msg(ctx, puzzle).await?,
             style,
         })
     }
 
-    pub async fn loading_msg(ctx: Context<'_>, puzzle: &Puzzle) -> Result<Message> {
-        let msg = if puzzle.is_daily() && ctx.in_guild() {
-            ctx.reply_ephemeral("you can't play a daily wordle in a server - check your dms!")
-                .await
-                .map_err(serenity_prelude::Error::from)?;
+    /// Carries on with a game's message from before the bot restarted.
+    pub fn resume(msg: Message, style: GameStyle) -> Self {
+        Self { msg, style }
+    }
+
+    pub async fn loading_msg<const N: usize>(
+        ctx: Context<'_>,
+        puzzle: &Puzzle<N>,
+    ) -> Result<Message> {
+        let msg = if (puzzle.is_daily() || puzzle.is_challenge()) && ctx.in_guild() {
+            ctx.reply_ephemeral(format!(
+                "you can't play {kind} in a server - check your dms!",
+                kind = if puzzle.is_daily() {
+                    "a daily wordle"
+                } else {
+                    "a challenge"
+                }
+            ))
+            .await
+            .map_err(serenity_prelude::Error::from)?;
 
             ctx.author()
                 .dm(ctx, CreateMessage::new().content("loading..."))
@@ -101,12 +204,12 @@ impl GameMessage {
         Ok(msg)
     }
 
-    pub async fn edit(
+    pub async fn edit<const N: usize>(
         &mut self,
         cache_http: impl CacheHttp,
-        data: impl AsRef<GameData>,
+        data: impl AsRef<GameData<N>>,
     ) -> Result<()> {
-        let builder = Self::builder(data, self.style);
+        let builder = Self::builder::<N>(data, self.style);
         self.msg.edit(cache_http, builder).await?;
 
         Ok(())
@@ -118,13 +221,13 @@ impl GameMessage {
         Ok(())
     }
 
-    pub async fn _resend(&mut self, ctx: GameContext<'_>) -> Result<()> {
+    pub async fn _resend<const N: usize>(&mut self, ctx: GameContext<'_, N>) -> Result<()> {
         self._delete(ctx).await?;
         //self.reply_loading(ctx).await?;
         Ok(())
     }
 
-    pub fn stop_buttons(data: impl AsRef<GameData>) -> CreateActionRow {
+    pub fn stop_buttons<const N: usize>(data: impl AsRef<GameData<N>>) -> CreateActionRow {
         let pause_cancel_button = if data.as_ref().puzzle.is_daily() {
             CreateButton::new("pause")
                 .emoji(ReactionType::Unicode("⏸️".to_owned()))
@@ -147,15 +250,41 @@ impl GameMessage {
         CreateActionRow::Buttons(buttons)
     }
 
+    /// Replaces the game buttons with one to analyze the finished game.
+    pub async fn show_analysis_button(&mut self, cache_http: impl CacheHttp) -> Result<()> {
+        let analysis = CreateButton::new("analysis")
+            .emoji(ReactionType::Unicode("📊".to_owned()))
+            .label("analysis")
+            .style(poise::serenity_prelude::ButtonStyle::Secondary);
+
+        self.msg
+            .edit(
+                cache_http,
+                EditMessage::new().components(vec![CreateActionRow::Buttons(vec![analysis])]),
+            )
+            .await
+    }
+
+    pub async fn remove_buttons(&mut self, cache_http: impl CacheHttp) -> Result<()> {
+        self.msg
+            .edit(cache_http, EditMessage::new().components(Vec::new()))
+            .await
+    }
+
     pub fn info_buttons() -> CreateActionRow {
+        let guess = CreateButton::new("guess")
+            .emoji(ReactionType::Unicode("✏️".to_owned()))
+            .label("guess")
+            .style(poise::serenity_prelude::ButtonStyle::Primary);
+
         let unused = CreateButton::new("unused")
             .emoji(ReactionType::Unicode("🔎".to_owned()))
             .label("unused letters");
 
-        CreateActionRow::Buttons(vec![unused])
+        CreateActionRow::Buttons(vec![guess, unused])
     }
 
-    pub fn buttons(data: impl AsRef<GameData>) -> Vec<CreateActionRow> {
-        vec![Self::stop_buttons(data), Self::info_buttons()]
+    pub fn buttons<const N: usize>(data: impl AsRef<GameData<N>>) -> Vec<CreateActionRow> {
+        vec![Self::stop_buttons::<N>(data), Self::info_buttons()]
     }
 }
@@ -1,24 +1,34 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use poise::serenity_prelude::{
     self, futures::StreamExt, CacheHttp, ChannelId, ComponentInteraction,
-    CreateInteractionResponseMessage, Http, Message, MessageId, ReactionType, ShardMessenger,
-    UserId,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    CreateMessage, GuildId, Http, Message, MessageId, ReactionType, ShardMessenger, User, UserId,
 };
+use tracing::warn;
 
 use crate::{
+    framework::config::WordleConfig,
     utils::serenity::{
         buttons::YesNoButtons,
         component_interaction::ComponentInteractionExt as UtilsComponentInteractionExt,
     },
-    Context,
+    Context, PoiseData,
 };
 
-use self::{message::GameMessage, options::GameOptions};
-
-use super::{puzzle::Puzzle, AsEmoji, DailyWordles};
+use self::{hard_mode::letter_char, message::GameMessage, options::GameOptions};
 
-type SerenityResult<T> = serenity_prelude::Result<T>;
+use super::{
+    active::SavedGame, analysis, daily::daily_command, data::HasLength, format_time, modal,
+    puzzle::Puzzle, sent_at, ActiveGame, ActiveGames, ArchivedGames, AsEmoji, Challenges,
+    DailyWordles, Language, LanguageData, Speedruns, Timer, WordleData,
+};
 
 mod cache;
 pub use cache::GamesCache;
@@ -32,46 +42,135 @@ pub use record::GameRecord;
 mod users;
 use users::Users;
 
+mod coop;
+
+mod boards;
+use boards::Boards;
+
+mod submit;
+pub use submit::{check, submit, GuessError, Submitter};
+
+mod spectator;
+use spectator::Spectator;
+
+pub mod hard_mode;
+
 pub mod options;
 use options::GameStyle;
 
 mod message;
 
-pub struct Game<'a> {
-    puzzle: Arc<Puzzle>,
-    guesses: kwordle::Guesses<5>,
-    ctx: Context<'a>,
+mod restore;
+pub use restore::restore_interrupted;
+
+/// How long the analysis button stays up after a game.
+const ANALYSIS_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+pub struct Game<'a, const N: usize = 5> {
+    puzzle: Arc<Puzzle<N>>,
+    guesses: kwordle::Guesses<N>,
+    serenity: &'a serenity_prelude::Context,
+    config: &'a WordleConfig,
+    /// Where the game was started, or `None` in DMs.
+    guild: Option<GuildId>,
     msg: GameMessage,
-    words: &'a kwordle::WordsList<5>,
+    words: &'a kwordle::WordsList<N>,
     dailies: &'a DailyWordles,
-    cache: &'a GamesCache,
+    active_games: &'a ActiveGames,
+    archive: &'a ArchivedGames,
+    challenges: &'a Challenges,
+    speedruns: &'a Speedruns,
+    cache: &'a GamesCache<N>,
     users: Users<'a>,
-    _style: GameStyle,
+    style: GameStyle,
+    hard_mode: bool,
+    idle_timeout: Option<Duration>,
+    hints: Arc<AtomicUsize>,
+    authors: Vec<UserId>,
+    boards: Option<Boards<N>>,
+    submitter: Option<Submitter>,
+    language: Option<&'a LanguageData>,
+    /// Shared with hints, when the words list isn't the usual one.
+    own_words: Option<Arc<kwordle::WordsList<N>>>,
+    spectator: Option<Spectator>,
+    timer: Option<Timer>,
+    finished: bool,
+    gave_up: bool,
 }
 
-impl<'a> Game<'a> {
+impl<'a, const N: usize> Game<'a, N>
+where
+    WordleData: HasLength<N>,
+{
     pub async fn new(
         ctx: Context<'a>,
-        puzzle: impl Into<Puzzle>,
+        puzzle: impl Into<Puzzle<N>>,
         options: GameOptions,
     ) -> serenity_prelude::Result<Self> {
-        let users = Users::new(ctx.author());
-
-        let data = ctx.data();
-        let puzzle = puzzle.into();
+        let puzzle: Puzzle<N> = puzzle.into();
         let msg = GameMessage::new(ctx, &puzzle, options.style).await?;
 
-        Ok(Self {
+        Ok(Self::with_message(
+            ctx.serenity_context(),
+            ctx.data(),
+            ctx.author(),
+            ctx.guild_id(),
+            puzzle,
+            options,
+            msg,
+        ))
+    }
+
+    fn with_message(
+        serenity: &'a serenity_prelude::Context,
+        data: &'a PoiseData,
+        owner: &'a User,
+        guild: Option<GuildId>,
+        puzzle: Puzzle<N>,
+        options: GameOptions,
+        msg: GameMessage,
+    ) -> Self {
+        let length = HasLength::<N>::length(data.wordle())
+            .expect("word length should be checked before starting a game");
+
+        let boards = puzzle.is_multi().then(|| Boards::new(puzzle.answers()));
+
+        Self {
             puzzle: Arc::new(puzzle),
             guesses: kwordle::Guesses::new(options.guesses_limit),
-            ctx,
+            serenity,
+            config: &data.config().wordle,
+            guild,
             msg,
-            words: data.wordle().words(),
-            dailies: data.wordle().wordles(),
-            cache: data.wordle().game_data(),
-            users,
-            _style: options.style,
-        })
+            words: length.words(),
+            dailies: data.wordle().wordles_in(guild),
+            active_games: data.wordle().active_games(),
+            archive: data.wordle().archive(),
+            challenges: data.wordle().challenges(),
+            speedruns: data.wordle().speedruns(),
+            cache: length.game_data(),
+            users: Users::new(owner),
+            style: options.style,
+            hard_mode: options.hard_mode,
+            idle_timeout: data.config().wordle.idle_timeout(),
+            hints: Arc::default(),
+            authors: Vec::new(),
+            boards,
+            submitter: None,
+            language: None,
+            own_words: None,
+            spectator: None,
+            timer: None,
+            finished: false,
+            gave_up: false,
+        }
+    }
+
+    /// Continues from the guesses of an unfinished game. Has to be called before [`Self::setup`].
+    pub fn resume(&mut self, record: &GameRecord<N>) {
+        self.guesses = record.guesses(self.guesses.max());
+        self.hints.store(record.hints, Ordering::Relaxed);
+        self.authors = record.authors();
     }
 
     pub fn channel_id(&self) -> ChannelId {
@@ -82,7 +181,7 @@ impl<'a> Game<'a> {
         *self.as_ref()
     }
 
-    pub async fn lock_channel(&self) -> Arc<GameData> {
+    pub async fn lock_channel(&self) -> Arc<GameData<N>> {
         self.update_data().await
     }
 
@@ -90,35 +189,135 @@ impl<'a> Game<'a> {
         self.cache.unlock_channel(self.channel_id()).await
     }
 
-    pub async fn update_data(&self) -> Arc<GameData> {
+    pub async fn update_data(&self) -> Arc<GameData<N>> {
         self.cache.set(self.channel_id(), self.data()).await;
         self.cache.get(self.channel_id()).await.expect("just added")
     }
 
-    pub async fn setup(&mut self) -> SerenityResult<()> {
+    pub async fn setup(&mut self) -> Result<(), crate::errors::CommandError> {
+        let ctx = self.context();
+
         let arc = self.lock_channel().await;
-        self.msg.edit(self.ctx, arc).await?;
+        self.msg.edit(ctx, arc).await?;
+
+        self.active_games.save(&self.active()).await?;
+
         Ok(())
     }
 
-    fn context(&self) -> GameContext<'a> {
+    /// Everything needed to pick the game back up if the bot restarts.
+    fn active(&self) -> ActiveGame {
+        let game = SavedGame {
+            owner: self.author_id(),
+            guild: self.guild,
+            length: N,
+            language: self.language(),
+            puzzle: self.puzzle.save(),
+            options: GameOptions {
+                style: self.style,
+                guesses_limit: self.guesses.max(),
+                hard_mode: self.hard_mode,
+            },
+            guesses: self
+                .guesses
+                .iter()
+                .map(|guess| {
+                    guess
+                        .into_iter()
+                        .map(|(letter, _)| letter_char(letter))
+                        .collect()
+                })
+                .collect(),
+            authors: self.authors.clone(),
+            hints: self.hints.load(Ordering::Relaxed),
+            timer: self.timer,
+            spectator: self.spectator.as_ref().map(Spectator::location),
+        };
+
+        ActiveGame::new(self.channel_id(), self.message_id(), &self.puzzle, game)
+    }
+
+    /// Mirrors the board's colors in a server channel. Has to be called after [`Self::setup`].
+    pub async fn spectate(
+        &mut self,
+        channel: ChannelId,
+    ) -> Result<(), crate::errors::CommandError> {
+        self.spectator = Some(
+            Spectator::start(
+                self.context(),
+                channel,
+                &self.users.owner().name,
+                &self.puzzle.title(),
+                &self.guesses,
+            )
+            .await?,
+        );
+
+        self.active_games.save(&self.active()).await?;
+
+        Ok(())
+    }
+
+    /// Times the game from the first guess to the one that solves it. Has to be called before [`Self::setup`].
+    pub fn timed(mut self) -> Self {
+        self.timer = Some(Timer::default());
+        self
+    }
+
+    /// How long a timed game took to solve, in milliseconds.
+    pub fn elapsed(&self) -> Option<i64> {
+        self.timer.as_ref().and_then(Timer::elapsed)
+    }
+
+    fn context(&self) -> GameContext<'a, N> {
         GameContext {
-            poise: self.ctx,
+            serenity: self.serenity,
+            user: self.author_id(),
             words_list: self.words,
         }
     }
 
+    /// The language the game is played in.
+    fn language(&self) -> Language {
+        self.language
+            .map(LanguageData::language)
+            .unwrap_or_default()
+    }
+
+    /// Says what the answer was, spelled the way the game's language spells it.
+    fn reveal(&self) -> String {
+        self.language.map_or_else(
+            || self.puzzle.reveal(),
+            |language| language.reveal(&self.puzzle),
+        )
+    }
+
+    /// How to carry on with the game's daily.
+    fn daily_command(&self) -> String {
+        daily_command(self.language(), self.dailies.guild())
+    }
+
     pub fn author_id(&self) -> UserId {
-        self.ctx.author().id
+        self.users.owner().id
     }
 
-    pub fn puzzle(&self) -> Arc<Puzzle> {
+    pub fn puzzle(&self) -> Arc<Puzzle<N>> {
         self.puzzle.clone()
     }
 
-    pub fn guess(&mut self, word: kwordle::Word<5>) -> kwordle::Guess<5> {
+    pub fn guess(&mut self, word: kwordle::Word<N>, author: UserId) -> kwordle::Guess<N> {
+        if let Puzzle::Absurdle(absurdle) = &*self.puzzle {
+            self.puzzle = Arc::new(Puzzle::Absurdle(absurdle.respond(&word, self.words)));
+        }
+
         let new = self.puzzle.guess(&word);
         self.guesses.push(new);
+        self.authors.push(author);
+
+        if let Some(boards) = &mut self.boards {
+            boards.guess(word);
+        }
+
         self.guesses.latest().expect("just added one")
     }
 
@@ -132,51 +331,206 @@ impl<'a> Game<'a> {
     // }
 
     pub fn is_solved(&self) -> bool {
-        self.guesses.latest_is_correct()
+        match &self.boards {
+            Some(boards) => boards.is_solved(),
+            None => self.guesses.latest_is_correct(),
+        }
+    }
+
+    pub fn out_of_guesses(&self) -> bool {
+        self.guesses
+            .max()
+            .is_some_and(|max| self.guesses.count() >= max)
+    }
+
+    pub fn state(&self, finished: bool) -> GameRecord<N> {
+        GameRecord::new(
+            self.author_id(),
+            self.guesses.clone(),
+            finished,
+            self.hard_mode,
+        )
+        .with_hints(self.hints.load(Ordering::Relaxed))
+        .with_gave_up(self.gave_up)
+        .with_authors(self.authors.clone())
+        .with_answer(self.settled_answer())
     }
 
-    pub fn state(&self, finished: bool) -> GameRecord {
-        GameRecord::new(self.author_id(), self.guesses.clone(), finished)
+    /// An absurdle's answer, once it's down to one.
+    /// Other puzzles keep their answer wherever the game is saved.
+    fn settled_answer(&self) -> Option<String> {
+        match &*self.puzzle {
+            Puzzle::Absurdle(absurdle) if absurdle.is_fixed() => {
+                Some(absurdle.answer().to_string())
+            }
+            _ => None,
+        }
     }
 
-    pub fn data(&self) -> GameData {
+    /// Saves the game's progress, if it's a daily, an archived daily or a challenge.
+    async fn save(&self, finished: bool) -> Result<(), crate::errors::CommandError> {
+        if let Some(number) = self.puzzle.number() {
+            self.dailies.update(number, self.state(finished)).await?;
+        } else if let Some(number) = self.puzzle.archive_number() {
+            self.archive
+                .update(self.dailies.guild(), number, self.state(finished))
+                .await?;
+        } else if let Some(number) = self.puzzle.challenge_number() {
+            self.challenges.update(number, self.state(finished)).await?;
+        }
+
+        Ok(())
+    }
+
+    pub fn data(&self) -> GameData<N> {
         GameData {
+            owner: self.author_id(),
             puzzle: self.puzzle(),
             guesses: self.guesses.clone(),
             message_id: self.message_id(),
+            hard_mode: self.hard_mode,
+            hints: self.hints.clone(),
+            authors: self.authors.clone(),
+            boards: self.boards.clone(),
+            submitter: self.submitter.clone(),
+            language: self.language(),
+            words: self.own_words.clone(),
+            timer: self.timer,
         }
     }
-    pub async fn run(&mut self) -> Result<(), crate::errors::CommandError> {
+    fn check_guess(&self, content: &str) -> Result<kwordle::Word<N>, GuessError> {
+        submit::check(self.words, content, self.hard_mode.then_some(&self.guesses))
+    }
+
+    /// Plays a guess that's already been checked.
+    /// If it ended the game, gives what to say about it.
+    async fn play(
+        &mut self,
+        word: kwordle::Word<N>,
+        author: &User,
+        sent: i64,
+    ) -> Result<Option<String>, crate::errors::CommandError> {
+        let ctx = self.context();
+
+        self.guess(word, author.id);
+
+        let solved = self.is_solved();
+        if let Some(timer) = &mut self.timer {
+            timer.record(sent, solved);
+        }
+
+        let data = self.cache.set(self.channel_id(), self.data()).await;
+        self.msg.edit(ctx, data).await?;
+
+        if let Some(spectator) = &mut self.spectator {
+            spectator.update(ctx, &self.guesses).await;
+        }
+
+        self.save(self.is_solved() || self.out_of_guesses()).await?;
+        self.active_games.save(&self.active()).await?;
+
+        if !self.users.contains(author.id) {
+            self.users.add(author.clone())
+        }
+
+        if self.is_solved() {
+            self.finished = true;
+            Ok(Some(self.elapsed().map_or_else(
+                || "you win!".to_owned(),
+                |elapsed| format!("you win in {}!", format_time(elapsed)),
+            )))
+        } else if self.out_of_guesses() {
+            self.msg.finish(ctx, "game over!").await?;
+            self.finished = true;
+            Ok(Some(self.reveal()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn run(&mut self) -> Result<(), crate::errors::CommandError> {
         let ctx = self.context();
 
         let mut messages = self.msg.replies_stream(ctx);
         let mut interactions = self.msg.buttons_stream(ctx);
+        let mut modals = self.msg.modals_stream(ctx);
+
+        // guesses from `/wordle guess` find the game through the cache
+        let (submitter, mut submissions) = tokio::sync::mpsc::unbounded_channel();
+        self.submitter = Some(submitter);
+        self.update_data().await;
+
+        let idle_timeout = self.idle_timeout.unwrap_or_default();
+        let idle = tokio::time::sleep(idle_timeout);
+        tokio::pin!(idle);
 
         loop {
             tokio::select! {
                 Some(msg) = messages.next() => {
-                    if let Some(word) = msg.find_guess(ctx).await? {
-                        self.guess(word);
-
-                        let data = self.cache.set(*self.msg.channel_id(), self.data()).await;
-                        self.msg.edit(ctx, data).await?;
+                    let hard_mode = self.hard_mode.then_some(&self.guesses);
 
-                        if let Some(num) = self.puzzle.number() {
-                            self.dailies.update(num, self.state(self.is_solved())).await?;
-                        }
+                    if let Some(word) = msg.find_guess(ctx, hard_mode).await? {
+                        idle.as_mut().reset(tokio::time::Instant::now() + idle_timeout);
 
-                        if self.is_solved() {
-                            msg.reply(ctx, "you win!").await?;
+                        if let Some(ending) = self.play(word, &msg.author, sent_at(msg.id.get())).await? {
+                            msg.reply(ctx, ending).await?;
                             break;
                         }
+                    }
+                },
+                Some(interaction) = modals.next() => {
+                    let content = modal::guess_input(&interaction).unwrap_or_default();
 
-                        if !self.users.contains(msg.author.id) {
-                            self.users.add(msg.author)
+                    match self.check_guess(&content) {
+                        Ok(word) => {
+                            interaction.create_response(ctx, CreateInteractionResponse::Acknowledge).await?;
+                            idle.as_mut().reset(tokio::time::Instant::now() + idle_timeout);
+
+                            if let Some(ending) = self.play(word, &interaction.user, sent_at(interaction.id.get())).await? {
+                                self.msg.reply(ctx, ending).await?;
+                                break;
+                            }
+                        }
+                        Err(err) => {
+                            interaction
+                                .create_response(
+                                    ctx,
+                                    CreateInteractionResponse::Message(
+                                        CreateInteractionResponseMessage::new().content(err.to_string()).ephemeral(true),
+                                    ),
+                                )
+                                .await?;
                         }
                     }
                 },
+                Some(submission) = submissions.recv() => {
+                    match self.check_guess(&submission.content) {
+                        Ok(word) => {
+                            let user = submission.user.clone();
+                            let sent = submission.sent;
+                            submission.reply(Ok(()));
+                            idle.as_mut().reset(tokio::time::Instant::now() + idle_timeout);
+
+                            if let Some(ending) = self.play(word, &user, sent).await? {
+                                self.msg.reply(ctx, ending).await?;
+                                break;
+                            }
+                        }
+                        Err(err) => submission.reply(Err(err)),
+                    }
+                },
                 Some(interaction) = interactions.next() => {
+                    idle.as_mut().reset(tokio::time::Instant::now() + idle_timeout);
+
                     match interaction.custom_id() {
+                        "guess" => {
+                            interaction
+                                .create_response(
+                                    ctx,
+                                    CreateInteractionResponse::Modal(modal::guess_modal(self.msg.modal_id(), N)),
+                                )
+                                .await?;
+                        }
                         "unused" => {
                             interaction.reply_ephemeral(ctx, format!("unused letters: {}", self.guesses.unused_letters().as_emoji())).await?;
                         }
@@ -192,13 +546,13 @@ impl<'a> Game<'a> {
                                         break;
                                     }
                                     "give_up" => {
-                                        if let Some(num) = self.puzzle().number() {
-                                            self.dailies.update(num, self.state(true)).await?;
-                                        }
+                                        self.gave_up = true;
+                                        self.save(true).await?;
 
-                                        self.msg.reply(ctx, format!("the word was: {word}", word = self.puzzle.answer())).await?;
+                                        self.msg.reply(ctx, self.reveal()).await?;
 
                                         self.msg.finish(ctx, "game over!").await?;
+                                        self.finished = true;
                                         break;
                                     },
                                     _ => unreachable!()
@@ -207,60 +561,281 @@ impl<'a> Game<'a> {
                         }
                     }
                 }
+                () = &mut idle, if self.idle_timeout.is_some() => {
+                    let minutes = idle_timeout.as_secs() / 60;
+
+                    if self.puzzle.is_daily() {
+                        self.save(false).await?;
+                        self.msg.close(ctx, format!("paused after {minutes} minutes without a guess! continue it with {command}", command = self.daily_command())).await?;
+                    } else if let Some(number) = self.puzzle.archive_number() {
+                        self.save(false).await?;
+                        self.msg.close(ctx, format!("paused after {minutes} minutes without a guess! continue it with `/wordle archive {number}`")).await?;
+                    } else if let Some(number) = self.puzzle.challenge_number() {
+                        self.save(false).await?;
+                        self.msg.close(ctx, format!("paused after {minutes} minutes without a guess! continue it with `/wordle accept {number}`")).await?;
+                    } else {
+                        self.msg.close(ctx, format!("canceled after {minutes} minutes without a guess!")).await?;
+                    }
+
+                    break;
+                }
             }
         }
 
+        if self.finished && coop::is_coop(self.author_id(), &self.authors) {
+            self.msg
+                .finish(
+                    ctx,
+                    format!("\n{}", coop::summary(&self.authors, self.is_solved())),
+                )
+                .await?;
+        }
+
+        if let Some(mut spectator) = self.spectator.take() {
+            let result = if self.is_solved() {
+                "solved it!"
+            } else if self.finished {
+                "didn't get it this time!"
+            } else {
+                "stopped playing for now"
+            };
+
+            spectator.finish(ctx, &self.guesses, result).await;
+        }
+
         self.unlock_channel().await;
+        self.active_games.remove(self.channel_id()).await?;
+
+        Ok(())
+    }
+
+    /// Runs the game, then follows up on however it went.
+    pub async fn play_out(&mut self) -> Result<(), crate::errors::CommandError> {
+        self.run().await?;
+
+        self.announce_daily().await?;
+        self.report_challenge().await?;
+        self.record_speedrun().await?;
+        self.show_share_code().await?;
+
+        self.offer_analysis().await
+    }
+
+    /// Says how to play a random game's word again, however the game ended.
+    async fn show_share_code(&mut self) -> Result<(), crate::errors::CommandError> {
+        let Some(code) = self.puzzle.share_code() else {
+            return Ok(());
+        };
+
+        let ctx = self.context();
+        let text = format!(
+            "play this word again with `/wordle random code:{code}{language}`",
+            language = self.language.map_or(String::new(), |language| format!(
+                " language:{}",
+                language.language().name()
+            ))
+        );
+
+        // finished games keep their buttons until the analysis is offered
+        if self.finished {
+            self.msg.finish(ctx, format!("\n{text}")).await?;
+        } else {
+            self.msg.close(ctx, text).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Tells the server's wordle channel about the daily.
+    async fn announce_daily(&self) -> Result<(), crate::errors::CommandError> {
+        let Some(number) = self.puzzle.number() else {
+            return Ok(());
+        };
+
+        if let Some(completed) = self.dailies.find_game(self.author_id(), number).await?
+            && let Some(channel) = self.config.channel_in(self.dailies.guild())
+        {
+            channel
+                .say(
+                    self.context(),
+                    format!(
+                        "`{username}` **completed {language}wordle {number}!**\n{emojis}",
+                        username = self.users.owner().name,
+                        language = self.language.map_or(String::new(), |language| format!(
+                            "{} ",
+                            language.language().name()
+                        )),
+                        emojis = completed.as_emoji()
+                    ),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Lets whoever sent a challenge know how it went.
+    /// The player saw how it went in the game, so only the challenger needs telling.
+    async fn report_challenge(&self) -> Result<(), crate::errors::CommandError> {
+        let Some(number) = self.puzzle.challenge_number() else {
+            return Ok(());
+        };
+
+        if let Some(played) = self.challenges.find(number).await?
+            && let Some(result) = played.result(&self.users.owner().name)
+            && let Some(game) = &played.game
+        {
+            let message = CreateMessage::new().content(format!("{result}\n{}", game.as_emoji()));
+
+            if let Err(err) = played.challenger.dm(self.context(), message).await {
+                warn!(number, "couldn't send challenge result: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the time of a solved speedrun.
+    /// Only solves without hints, where every guess was the player's own,
+    /// count towards the server's fastest times.
+    async fn record_speedrun(&self) -> Result<(), crate::errors::CommandError> {
+        let record = self.state(true);
+        let solo = self
+            .authors
+            .iter()
+            .all(|author| *author == self.author_id());
+
+        if let Some(guild) = self.guild
+            && let Some(millis) = self.elapsed()
+            && record.hints == 0
+            && solo
+        {
+            self.speedruns
+                .save(
+                    guild,
+                    self.author_id(),
+                    N,
+                    millis,
+                    record.num_guesses,
+                    record.hard_mode,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Once the game is over, lets anyone in the channel see how each guess compared to the best one.
+    async fn offer_analysis(&mut self) -> Result<(), crate::errors::CommandError> {
+        // analysis only makes sense against a single answer that was set from the start
+        if !self.finished || self.puzzle.is_multi() || self.puzzle.is_absurdle() {
+            return Ok(());
+        }
+
+        let ctx = self.context();
+
+        self.msg.show_analysis_button(ctx).await?;
+
+        let mut buttons = self.msg.buttons_stream(ctx);
+        let interaction = tokio::time::timeout(ANALYSIS_TIMEOUT, buttons.next()).await;
+
+        if let Ok(Some(interaction)) = interaction {
+            interaction.defer(ctx).await?;
+
+            let guesses = self.guesses.clone();
+            let words = self.words.clone();
+            let answer = *self.puzzle.answer();
+
+            // comparing every candidate against every other one takes a moment
+            let steps = tokio::task::spawn_blocking(move || {
+                analysis::analyze_guesses(&guesses, &words, &answer)
+            })
+            .await
+            .expect("analysis shouldn't panic");
+
+            interaction
+                .create_followup(
+                    ctx,
+                    CreateInteractionResponseFollowup::new().content(format!(
+                        "**{title} analysis**\n{summary}",
+                        title = self.puzzle.title(),
+                        summary = analysis::summary(&steps)
+                    )),
+                )
+                .await?;
+        }
+
+        self.msg.remove_buttons(ctx).await?;
 
         Ok(())
     }
 }
 
-impl AsRef<ChannelId> for Game<'_> {
+impl<'a> Game<'a, 5> {
+    /// Plays with another language's words and dailies. Has to be called before [`Self::setup`].
+    pub fn in_language(mut self, language: &'a LanguageData) -> Self {
+        self.words = language.words();
+        self.dailies = language.wordles();
+        self.own_words = Some(language.shared_words());
+        self.language = Some(language);
+        self
+    }
+}
+
+impl<const N: usize> AsRef<ChannelId> for Game<'_, N> {
     fn as_ref(&self) -> &ChannelId {
         self.msg.channel_id()
     }
 }
 
-impl AsRef<MessageId> for Game<'_> {
+impl<const N: usize> AsRef<MessageId> for Game<'_, N> {
     fn as_ref(&self) -> &MessageId {
         self.msg.message_id()
     }
 }
 
 trait MessageExt {
-    async fn find_guess(
+    async fn find_guess<const N: usize>(
         &self,
-        ctx: GameContext<'_>,
-    ) -> serenity_prelude::Result<Option<kwordle::Word<5>>>;
+        ctx: GameContext<'_, N>,
+        hard_mode: Option<&kwordle::Guesses<N>>,
+    ) -> serenity_prelude::Result<Option<kwordle::Word<N>>>;
 }
 
 impl MessageExt for Message {
-    async fn find_guess(
+    async fn find_guess<const N: usize>(
         &self,
-        ctx: GameContext<'_>,
-    ) -> serenity_prelude::Result<Option<kwordle::Word<5>>> {
+        ctx: GameContext<'_, N>,
+        hard_mode: Option<&kwordle::Guesses<N>>,
+    ) -> serenity_prelude::Result<Option<kwordle::Word<N>>> {
         let question_mark: ReactionType = ReactionType::Unicode("❓".to_owned());
         let check_mark: ReactionType = ReactionType::Unicode("✅".to_owned());
+        let lock: ReactionType = ReactionType::Unicode("🔒".to_owned());
 
-        match kwordle::Word::from_str(ctx.words_list, &self.content) {
+        match submit::check(ctx.words_list, &self.content, hard_mode) {
             Ok(word) => {
                 self.react(ctx, check_mark).await?;
                 Ok(Some(word))
             }
-            Err(err) => match err {
-                kwordle::word::ParseWordError::NotInList { .. } => {
-                    self.react(ctx, question_mark).await?;
-                    Ok(None)
-                }
-                _ => Ok(None),
-            },
+            Err(GuessError::HardMode(violation)) => {
+                self.react(ctx, lock).await?;
+                self.reply(ctx, violation.to_string()).await?;
+                Ok(None)
+            }
+            Err(GuessError::NotInList) => {
+                self.react(ctx, question_mark).await?;
+                Ok(None)
+            }
+            Err(_) => Ok(None),
         }
     }
 }
 
 trait ComponentInteractionExt {
-    async fn confirmed(&self, ctx: GameContext) -> serenity_prelude::Result<bool>;
+    async fn confirmed<const N: usize>(
+        &self,
+        ctx: GameContext<'_, N>,
+    ) -> serenity_prelude::Result<bool>;
 
     async fn await_yes_no(
         &self,
@@ -269,7 +844,10 @@ trait ComponentInteractionExt {
 }
 
 impl ComponentInteractionExt for ComponentInteraction {
-    async fn confirmed(&self, ctx: GameContext<'_>) -> serenity_prelude::Result<bool> {
+    async fn confirmed<const N: usize>(
+        &self,
+        ctx: GameContext<'_, N>,
+    ) -> serenity_prelude::Result<bool> {
         if self.user.id != ctx.user_id() {
             self.reply_ephemeral(ctx, "you can only manage a game you started!")
                 .await?;
@@ -323,49 +901,47 @@ impl ComponentInteractionExt for ComponentInteraction {
 }
 
 #[derive(Copy, Clone)]
-struct GameContext<'a> {
-    poise: Context<'a>,
-    words_list: &'a kwordle::WordsList<5>,
+struct GameContext<'a, const N: usize = 5> {
+    serenity: &'a serenity_prelude::Context,
+    /// Whoever started the game.
+    user: UserId,
+    words_list: &'a kwordle::WordsList<N>,
 }
 
-impl<'a> GameContext<'a> {
-    fn poise_context(&self) -> Context<'a> {
-        self.poise
-    }
-
+impl<const N: usize> GameContext<'_, N> {
     fn user_id(&self) -> UserId {
-        self.poise_context().author().id
+        self.user
     }
 
-    fn words(&self) -> &kwordle::WordsList<5> {
+    fn words(&self) -> &kwordle::WordsList<N> {
         self.words_list
     }
 }
 
-impl CacheHttp for GameContext<'_> {
+impl<const N: usize> CacheHttp for GameContext<'_, N> {
     fn http(&self) -> &serenity_prelude::Http {
         self.as_ref()
     }
 
     fn cache(&self) -> Option<&std::sync::Arc<serenity_prelude::Cache>> {
-        self.poise_context().serenity_context().cache()
+        self.serenity.cache()
     }
 }
 
-impl AsRef<Http> for GameContext<'_> {
+impl<const N: usize> AsRef<Http> for GameContext<'_, N> {
     fn as_ref(&self) -> &Http {
-        self.poise_context().http()
+        self.serenity.http()
     }
 }
 
-impl AsRef<ShardMessenger> for GameContext<'_> {
+impl<const N: usize> AsRef<ShardMessenger> for GameContext<'_, N> {
     fn as_ref(&self) -> &ShardMessenger {
-        &self.poise_context().serenity_context().shard
+        &self.serenity.shard
     }
 }
 
-impl AsRef<kwordle::WordsList> for GameContext<'_> {
-    fn as_ref(&self) -> &kwordle::WordsList<5> {
+impl<const N: usize> AsRef<kwordle::WordsList<N>> for GameContext<'_, N> {
+    fn as_ref(&self) -> &kwordle::WordsList<N> {
         self.words()
     }
 }
@@ -1,7 +1,10 @@
-#[derive(Debug, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct GameOptions {
     pub style: GameStyle,
     pub guesses_limit: Option<usize>,
+    pub hard_mode: bool,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -10,6 +13,7 @@ impl Default for GameOptions {
         Self {
             style: GameStyle::default(),
             guesses_limit: None,
+            hard_mode: false,
         }
     }
 }
@@ -18,6 +22,7 @@ impl Default for GameOptions {
 pub struct GameOptionsBuilder {
     style: Option<GameStyle>,
     guesses_limit: Option<usize>,
+    hard_mode: Option<bool>,
 }
 
 impl GameOptionsBuilder {
@@ -26,20 +31,27 @@ impl GameOptionsBuilder {
         self
     }
 
-    pub fn _guesses_limit(mut self, limit: impl Into<Option<usize>>) -> Self {
+    pub fn guesses_limit(mut self, limit: impl Into<Option<usize>>) -> Self {
         self.guesses_limit = limit.into();
         self
     }
 
+    pub fn hard_mode(mut self, hard_mode: impl Into<Option<bool>>) -> Self {
+        self.hard_mode = hard_mode.into();
+        self
+    }
+
     pub fn build(self) -> GameOptions {
         GameOptions {
             style: self.style.unwrap_or_default(),
             guesses_limit: self.guesses_limit,
+            hard_mode: self.hard_mode.unwrap_or_default(),
         }
     }
 }
 
-#[derive(poise::ChoiceParameter, Debug, Clone, Copy, Default)]
+#[derive(poise::ChoiceParameter, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameStyle {
     #[name = "colors only"]
     #[name = "colors"]
@@ -62,4 +74,36 @@ pub enum GameStyle {
     #[name = "fix flags"]
     #[name = "fix_flags"]
     SpacedLetters,
+    #[name = "image"]
+    #[name = "picture"]
+    #[name = "png"]
+    Image,
+}
+
+/// How many words to guess at once.
+#[derive(poise::ChoiceParameter, Debug, Clone, Copy, Default)]
+pub enum BoardCount {
+    #[name = "dordle (2 words)"]
+    #[name = "dordle"]
+    #[name = "2"]
+    #[default]
+    Dordle,
+    #[name = "quordle (4 words)"]
+    #[name = "quordle"]
+    #[name = "4"]
+    Quordle,
+}
+
+impl BoardCount {
+    pub fn count(self) -> usize {
+        match self {
+            Self::Dordle => 2,
+            Self::Quordle => 4,
+        }
+    }
+
+    /// The usual six guesses, plus one for each extra word.
+    pub fn guesses_limit(self) -> usize {
+        self.count() + 5
+    }
 }
@@ -6,16 +6,38 @@ use serde::{Deserialize, Serialize};
 use super::super::AsEmoji;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameRecord {
+pub struct GameRecord<const N: usize = 5> {
     pub user: UserId,
-    guesses: Vec<kwordle::Guess>,
+    guesses: Vec<kwordle::Guess<N>>,
     pub num_guesses: usize,
     finished: bool,
     solved: bool,
+    /// Whether the player stopped with the give up button, rather than running out of guesses.
+    #[serde(default, skip_serializing_if = "Not::not")]
+    gave_up: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub hard_mode: bool,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hints: usize,
+    /// Who made each guess, kept only for games with more than one player.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<UserId>,
+    /// The answer, for puzzles like absurdle that only settle on one as they're played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
 }
 
-impl GameRecord {
-    pub fn new(owner: UserId, guesses: kwordle::Guesses, finished: bool) -> Self {
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl<const N: usize> GameRecord<N> {
+    pub fn new(
+        owner: UserId,
+        guesses: kwordle::Guesses<N>,
+        finished: bool,
+        hard_mode: bool,
+    ) -> Self {
         let count = guesses.count();
         let solved = guesses.latest_is_correct();
 
@@ -25,19 +47,75 @@ impl GameRecord {
             num_guesses: count,
             finished,
             solved,
+            gave_up: false,
+            hard_mode,
+            hints: 0,
+            authors: Vec::new(),
+            answer: None,
+        }
+    }
+
+    pub fn with_hints(mut self, hints: usize) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    pub fn with_gave_up(mut self, gave_up: bool) -> Self {
+        self.gave_up = gave_up;
+        self
+    }
+
+    pub fn with_answer(mut self, answer: Option<String>) -> Self {
+        self.answer = answer;
+        self
+    }
+
+    pub fn with_authors(mut self, authors: Vec<UserId>) -> Self {
+        if super::coop::is_coop(self.user, &authors) {
+            self.authors = authors;
         }
+
+        self
     }
 
     pub fn is_finished(&self) -> bool {
         self.finished
     }
 
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    pub fn gave_up(&self) -> bool {
+        self.gave_up
+    }
+
     pub fn in_progress(&self) -> bool {
         self.is_finished().not()
     }
+
+    /// Rebuilds the guesses, to continue a game from where it was left off.
+    pub fn guesses(&self, limit: Option<usize>) -> kwordle::Guesses<N> {
+        let mut guesses = kwordle::Guesses::new(limit);
+
+        for guess in &self.guesses {
+            guesses.push(guess.clone());
+        }
+
+        guesses
+    }
+
+    /// Who made each guess. Games with only one player are all theirs.
+    pub fn authors(&self) -> Vec<UserId> {
+        if self.authors.is_empty() {
+            vec![self.user; self.guesses.len()]
+        } else {
+            self.authors.clone()
+        }
+    }
 }
 
-impl AsEmoji for GameRecord {
+impl<const N: usize> AsEmoji for GameRecord<N> {
     fn as_emoji(&self) -> Cow<str> {
         self.guesses.as_emoji()
     }
@@ -50,3 +128,39 @@ impl AsEmoji for GameRecord {
         self.guesses.emoji_with_letters_spaced()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    use super::GameRecord;
+
+    fn record(guesses: &[&str]) -> GameRecord {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let mut played = kwordle::Guesses::new(None);
+
+        for guess in guesses {
+            played.push(answer.guess(kwordle::Word::from_str(&words, guess).unwrap()));
+        }
+
+        GameRecord::new(UserId::new(1), played, true, false)
+    }
+
+    #[test]
+    fn solo_authors() {
+        let game = record(&["chair", "onion"]).with_authors(vec![UserId::new(1); 2]);
+
+        assert_eq!(game.authors(), vec![UserId::new(1); 2]);
+        assert!(game.authors.is_empty());
+    }
+
+    #[test]
+    fn someone_else_guessed() {
+        let game = record(&["chair", "onion"]).with_authors(vec![UserId::new(2); 2]);
+
+        assert_eq!(game.authors(), vec![UserId::new(2); 2]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::sync::atomic::Ordering;
+
+use poise::serenity_prelude::{ChannelId, Context, Message, MessageId, User};
+use tracing::{error, warn};
+
+use crate::{errors::CommandError, PoiseData};
+
+use super::{
+    super::{
+        active::SavedGame, data::HasLength, words_list, ActiveGame, DbResult, Puzzle, WordleData,
+    },
+    message::GameMessage,
+    spectator::Spectator,
+    Game,
+};
+
+/// Picks back up the games saved in the database that aren't running anymore,
+/// because the bot restarted while they were being played.
+/// Games that can't be picked back up are expired instead.
+///
+/// This runs every time the bot connects, so each game is claimed before it's restored,
+/// and a reconnect or another shard can't start it twice. Restored games save themselves
+/// again once they're set up.
+pub async fn restore_interrupted(serenity: &Context, data: &PoiseData) -> DbResult<()> {
+    for saved in data.wordle().active_games().all().await? {
+        if data.wordle().active_game(saved.channel).await.is_some() {
+            continue;
+        }
+
+        let Some(active) = data.wordle().active_games().claim(saved.channel).await? else {
+            continue;
+        };
+
+        let (serenity, data) = (serenity.clone(), data.clone());
+
+        // each game runs until it's over, like it would from its command
+        tokio::spawn(async move {
+            if let Err(err) = restore(&serenity, &data, active).await {
+                error!("failed to restore interrupted wordle: {err}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn restore(
+    serenity: &Context,
+    data: &PoiseData,
+    active: ActiveGame,
+) -> Result<(), CommandError> {
+    let restored = match active.saved() {
+        Some(saved) => resume(serenity, data, &active, saved).await?,
+        None => false,
+    };
+
+    if !restored {
+        let guild = active.saved().and_then(|game| game.guild);
+
+        if let Err(err) = active
+            .expire(serenity, data.wordle().wordles_in(guild).guild())
+            .await
+        {
+            warn!(channel = %active.channel, "couldn't expire interrupted wordle: {err}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Plays a saved game out on its old message.
+/// Gives `false` if it can't be rebuilt.
+async fn resume(
+    serenity: &Context,
+    data: &PoiseData,
+    active: &ActiveGame,
+    saved: &SavedGame,
+) -> Result<bool, CommandError> {
+    // the player or the game's message might be gone by now
+    let (Ok(owner), Ok(msg)) = (
+        saved.owner.to_user(serenity).await,
+        active.channel.message(serenity, active.message).await,
+    ) else {
+        return Ok(false);
+    };
+
+    match saved.length {
+        4 => play(Game::<4>::reopen(serenity, data, &owner, msg, saved), saved).await,
+        5 if saved.language.is_english() => {
+            play(Game::<5>::reopen(serenity, data, &owner, msg, saved), saved).await
+        }
+        5 => {
+            let game = Game::<5>::reopen(serenity, data, &owner, msg, saved)
+                .zip(data.wordle().language(saved.language))
+                .map(|(game, language)| game.in_language(language));
+
+            play(game, saved).await
+        }
+        6 => play(Game::<6>::reopen(serenity, data, &owner, msg, saved), saved).await,
+        7 => play(Game::<7>::reopen(serenity, data, &owner, msg, saved), saved).await,
+        _ => Ok(false),
+    }
+}
+
+async fn play<const N: usize>(
+    game: Option<Game<'_, N>>,
+    saved: &SavedGame,
+) -> Result<bool, CommandError>
+where
+    WordleData: HasLength<N>,
+{
+    let Some(mut game) = game else {
+        return Ok(false);
+    };
+
+    if !game.replay(saved) {
+        return Ok(false);
+    }
+
+    if let Some(location) = saved.spectator {
+        game.respectate(location).await;
+    }
+
+    game.setup().await?;
+    game.play_out().await?;
+
+    Ok(true)
+}
+
+impl<'a, const N: usize> Game<'a, N>
+where
+    WordleData: HasLength<N>,
+{
+    /// Rebuilds a game on its message from before the bot restarted.
+    /// Gives nothing if its word length isn't set up anymore, or its puzzle can't be rebuilt.
+    fn reopen(
+        serenity: &'a Context,
+        data: &'a PoiseData,
+        owner: &'a User,
+        msg: Message,
+        saved: &SavedGame,
+    ) -> Option<Self> {
+        let length = HasLength::<N>::length(data.wordle())?;
+        let puzzle = Puzzle::restore(&saved.puzzle, length.words())?;
+
+        Some(Self::with_message(
+            serenity,
+            data,
+            owner,
+            saved.guild,
+            puzzle,
+            saved.options,
+            GameMessage::resume(msg, saved.options.style),
+        ))
+    }
+
+    /// Plays the guesses from before the bot restarted. Has to be called before [`Self::setup`].
+    /// Gives `false` if one of them isn't a word of length `N`.
+    fn replay(&mut self, saved: &SavedGame) -> bool {
+        for (word, author) in saved.guesses.iter().zip(&saved.authors) {
+            let Some(word) = words_list::saved_word(word) else {
+                return false;
+            };
+
+            self.guess(word, *author);
+        }
+
+        self.hints.store(saved.hints, Ordering::Relaxed);
+        self.timer = saved.timer;
+
+        true
+    }
+
+    /// Carries on mirroring the board on the message it was on before the bot restarted.
+    async fn respectate(&mut self, location: (ChannelId, MessageId)) {
+        match Spectator::resume(
+            self.context(),
+            location,
+            &self.users.owner().name,
+            &self.puzzle.title(),
+        )
+        .await
+        {
+            Ok(spectator) => self.spectator = Some(spectator),
+            // the game itself can still go on
+            Err(err) => warn!("couldn't find spectator message: {err}"),
+        }
+    }
+}
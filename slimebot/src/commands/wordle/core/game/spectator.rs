@@ -0,0 +1,139 @@
+use poise::serenity_prelude::{CacheHttp, ChannelId, EditMessage, Message, MessageId};
+use tracing::warn;
+
+use super::super::AsEmoji;
+
+/// A copy of a daily game's board in a server channel, so others can follow along.
+/// It only shows colors, so it doesn't give the answer away.
+pub struct Spectator {
+    msg: Message,
+    name: String,
+    title: String,
+}
+
+impl Spectator {
+    pub async fn start<const N: usize>(
+        cache_http: impl CacheHttp,
+        channel: ChannelId,
+        name: &str,
+        title: &str,
+        guesses: &kwordle::Guesses<N>,
+    ) -> poise::serenity_prelude::Result<Self> {
+        let msg = channel
+            .say(cache_http, content(name, title, guesses, None))
+            .await?;
+
+        Ok(Self {
+            msg,
+            name: name.to_owned(),
+            title: title.to_owned(),
+        })
+    }
+
+    /// Carries on with a spectator message from before the bot restarted.
+    pub async fn resume(
+        cache_http: impl CacheHttp,
+        (channel, message): (ChannelId, MessageId),
+        name: &str,
+        title: &str,
+    ) -> poise::serenity_prelude::Result<Self> {
+        let msg = channel.message(cache_http, message).await?;
+
+        Ok(Self {
+            msg,
+            name: name.to_owned(),
+            title: title.to_owned(),
+        })
+    }
+
+    /// Where the spectator message is.
+    pub fn location(&self) -> (ChannelId, MessageId) {
+        (self.msg.channel_id, self.msg.id)
+    }
+
+    /// Shows the latest guesses.
+    pub async fn update<const N: usize>(
+        &mut self,
+        cache_http: impl CacheHttp,
+        guesses: &kwordle::Guesses<N>,
+    ) {
+        self.edit(cache_http, content(&self.name, &self.title, guesses, None))
+            .await;
+    }
+
+    /// Shows how the game ended.
+    pub async fn finish<const N: usize>(
+        &mut self,
+        cache_http: impl CacheHttp,
+        guesses: &kwordle::Guesses<N>,
+        result: &str,
+    ) {
+        self.edit(
+            cache_http,
+            content(&self.name, &self.title, guesses, Some(result)),
+        )
+        .await;
+    }
+
+    // losing the spectator message shouldn't stop the game
+    async fn edit(&mut self, cache_http: impl CacheHttp, content: String) {
+        if let Err(err) = self
+            .msg
+            .edit(cache_http, EditMessage::new().content(content))
+            .await
+        {
+            warn!("couldn't update spectator message: {err}");
+        }
+    }
+}
+
+fn content<const N: usize>(
+    name: &str,
+    title: &str,
+    guesses: &kwordle::Guesses<N>,
+    result: Option<&str>,
+) -> String {
+    let status = format!(
+        "{count}/{max}",
+        count = guesses.count(),
+        max = guesses.max().map_or("∞".to_owned(), |max| max.to_string())
+    );
+
+    match result {
+        Some(result) => format!(
+            "`{name}` played {title} {status}\n{board}\n{result}",
+            board = guesses.as_emoji()
+        ),
+        None => format!(
+            "`{name}` is playing {title} {status}\n{board}",
+            board = guesses.as_emoji()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::content;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn colors_only() {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let mut guesses = kwordle::Guesses::new(Some(6));
+        guesses.push(answer.guess(kwordle::Word::from_str(&words, "chair").unwrap()));
+
+        assert_eq!(
+            content("slime", "daily wordle 12", &guesses, None),
+            "`slime` is playing daily wordle 12 1/6\n⬛⬛⬛🟨⬛"
+        );
+
+        guesses.push(answer.guess(answer));
+
+        assert_eq!(
+            content("slime", "daily wordle 12", &guesses, Some("solved it!")),
+            "`slime` played daily wordle 12 2/6\n⬛⬛⬛🟨⬛\n🟩🟩🟩🟩🟩\nsolved it!"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use poise::serenity_prelude::User;
+use tokio::sync::{mpsc, oneshot};
+
+use super::{
+    super::language,
+    hard_mode::{self, Violation},
+};
+
+/// Why a guess wasn't played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    /// A real word, but not one in the words list.
+    NotInList,
+    /// Not a word at all, like the wrong number of letters.
+    Invalid,
+    HardMode(Violation),
+    /// The game ended before the guess got to it.
+    Over,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInList => write!(f, "that isn't in the words list!"),
+            Self::Invalid => write!(f, "that isn't a valid guess!"),
+            Self::HardMode(violation) => violation.fmt(f),
+            Self::Over => write!(f, "that game is already over!"),
+        }
+    }
+}
+
+/// Checks that a guess is in the words list and, in hard mode, follows the rules.
+/// Accents don't matter, so `canción` and `cancion` are the same guess.
+pub fn check<const N: usize>(
+    words: &kwordle::WordsList<N>,
+    content: &str,
+    hard_mode: Option<&kwordle::Guesses<N>>,
+) -> Result<kwordle::Word<N>, GuessError> {
+    let content = language::fold(content.trim());
+
+    if !content.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(GuessError::Invalid);
+    }
+
+    let word = kwordle::Word::from_str(words, &content).map_err(|err| match err {
+        kwordle::word::ParseWordError::NotInList { .. } => GuessError::NotInList,
+        _ => GuessError::Invalid,
+    })?;
+
+    if let Some(violation) = hard_mode.and_then(|guesses| hard_mode::check(guesses, &word)) {
+        return Err(GuessError::HardMode(violation));
+    }
+
+    Ok(word)
+}
+
+/// A guess sent to a running game from outside of it, like with `/wordle guess`.
+#[derive(Debug)]
+pub struct Submission {
+    pub user: User,
+    pub content: String,
+    /// When discord says the guess was sent, in milliseconds since the unix epoch.
+    pub sent: i64,
+    reply: oneshot::Sender<Result<(), GuessError>>,
+}
+
+impl Submission {
+    /// Says whether the guess was played.
+    pub fn reply(self, result: Result<(), GuessError>) {
+        // the command might have stopped waiting, which is fine
+        let _ = self.reply.send(result);
+    }
+}
+
+pub type Submitter = mpsc::UnboundedSender<Submission>;
+
+/// Sends a guess to a game and waits to hear whether it was played.
+pub async fn submit(
+    submitter: &Submitter,
+    user: User,
+    content: String,
+    sent: i64,
+) -> Result<(), GuessError> {
+    let (reply, result) = oneshot::channel();
+
+    submitter
+        .send(Submission {
+            user,
+            content,
+            sent,
+            reply,
+        })
+        .map_err(|_| GuessError::Over)?;
+
+    result.await.unwrap_or(Err(GuessError::Over))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, GuessError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn checks() {
+        let words = kwordle::classic::words_list();
+
+        assert_eq!(
+            check(&words, " onion ", None).map(|word| word.to_string()),
+            Ok("onion".to_owned())
+        );
+        assert_eq!(check(&words, "qqqqq", None), Err(GuessError::NotInList));
+        assert_eq!(check(&words, "on", None), Err(GuessError::Invalid));
+        assert_eq!(
+            check(&words, "ÓNÍON", None).map(|word| word.to_string()),
+            Ok("onion".to_owned())
+        );
+        assert_eq!(check(&words, "niños", None), Err(GuessError::Invalid));
+    }
+
+    #[test]
+    fn hard_mode() {
+        let words = kwordle::classic::words_list();
+        let answer = check(&words, "onion", None).unwrap();
+
+        let mut guesses = kwordle::Guesses::new(None);
+        guesses.push(answer.guess(check(&words, "chair", None).unwrap()));
+
+        assert!(matches!(
+            check(&words, "crane", Some(&guesses)),
+            Err(GuessError::HardMode(_))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::atomic::Ordering;
+
+use super::{
+    game::GameData,
+    solver::{self, Revealed},
+};
+
+#[derive(poise::ChoiceParameter, Debug, Clone, Copy, Default)]
+pub enum HintKind {
+    #[name = "answers left"]
+    #[name = "count"]
+    #[default]
+    Count,
+    #[name = "suggested guess"]
+    #[name = "guess"]
+    Guess,
+    #[name = "reveal a letter"]
+    #[name = "letter"]
+    Letter,
+}
+
+/// Gives a hint for a game, and counts it against the game.
+pub async fn hint<const N: usize>(
+    kind: HintKind,
+    data: &GameData<N>,
+    words: &kwordle::WordsList<N>,
+) -> String {
+    if data.puzzle.is_multi() {
+        return "hints aren't available when playing more than one board!".to_owned();
+    }
+
+    if data.puzzle.is_absurdle() {
+        return "hints aren't available in absurdle - the answer isn't picked yet!".to_owned();
+    }
+
+    data.hints.fetch_add(1, Ordering::Relaxed);
+
+    let revealed: Vec<Revealed> = data
+        .guesses
+        .iter()
+        .map(|guess| Revealed::from_guess(&guess))
+        .collect();
+
+    match kind {
+        HintKind::Count => {
+            let answers: Vec<String> = words.answers.iter().map(ToString::to_string).collect();
+            let count = solver::candidates(&revealed, answers.iter().map(String::as_str)).len();
+
+            match count {
+                1 => "there's only 1 possible answer left!".to_owned(),
+                count => format!("there are {count} possible answers left"),
+            }
+        }
+        HintKind::Guess => {
+            let answers: Vec<String> = words.answers.iter().map(ToString::to_string).collect();
+
+            // comparing every candidate against every other one takes a moment
+            let suggestion = tokio::task::spawn_blocking(move || {
+                let candidates = solver::candidates(&revealed, answers.iter().map(String::as_str));
+                solver::best_guess(&candidates).map(str::to_owned)
+            })
+            .await
+            .expect("solver shouldn't panic");
+
+            match suggestion {
+                Some(word) => format!("try guessing `{word}`"),
+                None => "none of the answers fit your guesses!".to_owned(),
+            }
+        }
+        HintKind::Letter => {
+            let answer = data.puzzle.answer().to_string();
+
+            let found = |position: usize| {
+                data.guesses.iter().any(|guess| {
+                    guess
+                        .into_iter()
+                        .nth(position)
+                        .is_some_and(|(_, state)| matches!(state, kwordle::LetterState::Correct))
+                })
+            };
+
+            let unrevealed = answer
+                .chars()
+                .enumerate()
+                .find(|&(position, _)| !found(position));
+
+            match unrevealed {
+                Some((position, letter)) => format!(
+                    "letter {position} is `{letter}`",
+                    position = position + 1,
+                    letter = letter.to_ascii_uppercase()
+                ),
+                None => "you've already found every letter!".to_owned(),
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashMap, sync::Arc};
+
+use mongodb::Database;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::framework::config::LanguageFiles;
+
+use super::{words_list, DailyWordles, Puzzle, Schedule};
+
+/// A language that wordles can be played in, besides the usual english.
+#[derive(
+    poise::ChoiceParameter, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[name = "english"]
+    #[default]
+    English,
+    #[name = "español"]
+    #[name = "spanish"]
+    Spanish,
+    #[name = "deutsch"]
+    #[name = "german"]
+    German,
+    #[name = "português"]
+    #[name = "portuguese"]
+    Portuguese,
+}
+
+impl Language {
+    /// What the language calls itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::English => "english",
+            Self::Spanish => "español",
+            Self::German => "deutsch",
+            Self::Portuguese => "português",
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+            Self::German => "de",
+            Self::Portuguese => "pt",
+        }
+    }
+
+    pub fn is_english(self) -> bool {
+        self == Self::English
+    }
+}
+
+/// The letters a word is played with, with accents and umlauts taken off
+/// so it can be typed either way. Letters of their own, like `ñ` and `ß`,
+/// are kept as they are, so the words list can report words that can't be played.
+pub fn fold(word: &str) -> String {
+    word.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Folds every word in a list, keeping how the answers are really spelled.
+fn fold_list(words: Vec<String>, spellings: &mut HashMap<String, String>) -> Vec<String> {
+    words
+        .into_iter()
+        .map(|word| {
+            let folded = fold(&word);
+
+            if folded != word {
+                spellings.entry(folded.clone()).or_insert(word);
+            }
+
+            folded
+        })
+        .collect()
+}
+
+/// The words list and daily wordles for a language.
+#[derive(Debug, Clone)]
+pub struct LanguageData {
+    language: Language,
+    words: Arc<kwordle::WordsList<5>>,
+    /// The real spelling of words that were folded.
+    spellings: Arc<HashMap<String, String>>,
+    wordles: DailyWordles,
+}
+
+impl LanguageData {
+    pub fn load(
+        db: &Database,
+        language: Language,
+        files: &LanguageFiles,
+        schedule: Schedule,
+    ) -> Result<Self, words_list::Error> {
+        let mut spellings = HashMap::new();
+
+        let guesses = fold_list(words_list::read_words(&files.guesses_file)?, &mut spellings);
+        let answers = fold_list(words_list::read_words(&files.answers_file)?, &mut spellings);
+
+        let words = words_list::build::<5>(guesses, answers, &files.answers_file)?;
+
+        info!(language = language.name(), "loaded language");
+
+        let wordles = DailyWordles::in_collection(
+            db,
+            &format!("daily_wordles_{}", language.code()),
+            schedule,
+        );
+
+        Ok(Self {
+            language,
+            words: Arc::new(words),
+            spellings: Arc::new(spellings),
+            wordles,
+        })
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn words(&self) -> &kwordle::WordsList<5> {
+        &self.words
+    }
+
+    pub fn shared_words(&self) -> Arc<kwordle::WordsList<5>> {
+        self.words.clone()
+    }
+
+    pub fn wordles(&self) -> &DailyWordles {
+        &self.wordles
+    }
+
+    /// How a word is really spelled.
+    pub fn spelling<'a>(&'a self, word: &'a str) -> &'a str {
+        self.spellings.get(word).map_or(word, String::as_str)
+    }
+
+    /// Says what the answer was, spelled properly.
+    pub fn reveal<const N: usize>(&self, puzzle: &Puzzle<N>) -> String {
+        let answers: Vec<String> = puzzle
+            .answers()
+            .iter()
+            .map(|answer| self.spelling(&answer.to_string()).to_owned())
+            .collect();
+
+        match answers.as_slice() {
+            [answer] => format!("the word was: {answer}"),
+            _ => puzzle.reveal(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{fold, fold_list, Language};
+    use poise::ChoiceParameter;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn config_names() {
+        assert_eq!(Language::from_name("spanish"), Some(Language::Spanish));
+        assert_eq!(Language::from_name("german"), Some(Language::German));
+        assert_eq!(
+            Language::from_name("portuguese"),
+            Some(Language::Portuguese)
+        );
+        assert_eq!(Language::from_name("klingon"), None);
+    }
+
+    #[test]
+    fn folds_diacritics() {
+        assert_eq!(fold("Canción"), "cancion");
+        assert_eq!(fold("MÜDEN"), "muden");
+        assert_eq!(fold("ação"), "acao");
+        assert_eq!(fold("onion"), "onion");
+    }
+
+    #[test]
+    fn keeps_own_letters() {
+        assert_eq!(fold("AÑOS"), "años");
+        assert_eq!(fold("größe"), "größe");
+    }
+
+    #[test]
+    fn keeps_spellings() {
+        let mut spellings = HashMap::new();
+
+        let words = fold_list(
+            vec!["árbol".to_owned(), "perro".to_owned(), "niños".to_owned()],
+            &mut spellings,
+        );
+
+        assert_eq!(words, vec!["arbol", "perro", "niños"]);
+        assert_eq!(spellings.get("arbol").map(String::as_str), Some("árbol"));
+        assert_eq!(spellings.get("perro"), None);
+        assert_eq!(spellings.get("niños"), None);
+    }
+}
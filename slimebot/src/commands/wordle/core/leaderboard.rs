@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+
+use chrono::Duration;
+use poise::serenity_prelude::UserId;
+use serde::Deserialize;
+
+use crate::framework::data::UtcDateTime;
+
+use super::stats::longest_run;
+
+#[derive(poise::ChoiceParameter, Debug, Clone, Copy, Default)]
+pub enum LeaderboardPeriod {
+    #[name = "last week"]
+    #[name = "week"]
+    #[default]
+    Week,
+    #[name = "last month"]
+    #[name = "month"]
+    Month,
+    #[name = "all time"]
+    #[name = "all"]
+    #[name = "all_time"]
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    pub fn duration(self) -> Option<Duration> {
+        match self {
+            Self::Week => Some(Duration::days(7)),
+            Self::Month => Some(Duration::days(30)),
+            Self::AllTime => None,
+        }
+    }
+
+    /// The earliest a daily can have started to count in this period, or `None` to count every daily.
+    pub fn start(self, now: UtcDateTime) -> Option<UtcDateTime> {
+        self.duration().map(|duration| now - duration)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Week => "in the last week",
+            Self::Month => "in the last month",
+            Self::AllTime => "of all time",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderboardEntry {
+    #[serde(rename = "_id")]
+    pub user: UserId,
+    pub played: u32,
+    pub solved: u32,
+    solved_guesses: u32,
+    #[serde(default)]
+    solved_puzzles: Vec<u32>,
+}
+
+impl LeaderboardEntry {
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.solved > 0).then(|| f64::from(self.solved_guesses) / f64::from(self.solved))
+    }
+
+    pub fn max_streak(&self) -> u32 {
+        longest_run(&self.solved_puzzles)
+    }
+
+    /// Orders entries from best to worst.
+    ///
+    /// More solves rank higher, then fewer average guesses, then a longer streak.
+    /// Any remaining ties are broken by user id, so the order is always the same.
+    pub fn ranking(&self, other: &Self) -> Ordering {
+        other
+            .solved
+            .cmp(&self.solved)
+            .then_with(|| {
+                let average = |entry: &Self| entry.average_guesses().unwrap_or(f64::INFINITY);
+                average(self).total_cmp(&average(other))
+            })
+            .then_with(|| other.max_streak().cmp(&self.max_streak()))
+            .then_with(|| self.user.cmp(&other.user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    use super::{LeaderboardEntry, LeaderboardPeriod};
+
+    #[test]
+    fn period_start() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            LeaderboardPeriod::Week.start(now),
+            Some(Utc.with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            LeaderboardPeriod::Month.start(now),
+            Some(Utc.with_ymd_and_hms(2024, 2, 9, 12, 0, 0).unwrap())
+        );
+        assert_eq!(LeaderboardPeriod::AllTime.start(now), None);
+    }
+
+    fn entry(user: u64, solved_guesses: &[u32], solved_puzzles: &[u32]) -> LeaderboardEntry {
+        LeaderboardEntry {
+            user: UserId::new(user),
+            played: solved_guesses.len() as u32,
+            solved: solved_guesses.len() as u32,
+            solved_guesses: solved_guesses.iter().sum(),
+            solved_puzzles: solved_puzzles.to_vec(),
+        }
+    }
+
+    #[test]
+    fn ranking() {
+        let mut entries = vec![
+            entry(1, &[4, 4], &[1, 3]),
+            entry(2, &[3, 3, 6], &[1, 2, 3]),
+            entry(3, &[4, 4], &[2, 3]),
+            entry(4, &[3, 5], &[1, 2]),
+            entry(5, &[3, 5], &[1, 2]),
+            entry(6, &[], &[]),
+        ];
+
+        entries.sort_by(LeaderboardEntry::ranking);
+
+        let order = entries
+            .iter()
+            .map(|entry| entry.user.get())
+            .collect::<Vec<_>>();
+
+        assert_eq!(order, vec![2, 3, 4, 5, 1, 6]);
+    }
+}
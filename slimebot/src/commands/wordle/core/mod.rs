@@ -10,19 +10,62 @@ use mongodb::error::Error as MongoDbError;
 mod puzzle;
 pub use puzzle::Puzzle;
 
+pub mod share_code;
+
 type DbResult<T> = std::result::Result<T, MongoDbError>;
 
-//mod words_list;
-//pub use words_list::WordsList;
+mod words_list;
+
+mod schedule;
+pub use schedule::Schedule;
 
 mod daily;
-pub use daily::DailyWordles;
+pub use daily::{DailyWordle, DailyWordles};
+
+mod language;
+pub use language::{Language, LanguageData};
+
+mod solver;
+
+mod analysis;
+
+mod absurdle;
+
+mod hint;
+pub use hint::HintKind;
+
+mod digest;
+pub use digest::digest;
+
+mod modal;
+
+pub mod render;
+
+pub mod race;
+
+mod archive;
+pub use archive::ArchivedGames;
+
+mod challenge;
+pub use challenge::{Challenge, Challenges};
+
+mod speedrun;
+pub use speedrun::{format_time, sent_at, Speedruns, Timer};
+
+mod active;
+pub use active::{ActiveGame, ActiveGames};
 
 pub mod game;
 pub use game::{Game, GameRecord};
 
 mod data;
-pub use data::WordleData;
+pub use data::{HasLength, WordLength, WordleData};
+
+mod stats;
+pub use stats::UserStats;
+
+mod leaderboard;
+pub use leaderboard::{LeaderboardEntry, LeaderboardPeriod};
 
 /*
 fn create_menu(daily_available: bool) -> CreateReply {
@@ -0,0 +1,29 @@
+use poise::serenity_prelude::{
+    ActionRowComponent, CreateActionRow, CreateInputText, CreateModal, InputTextStyle,
+    ModalInteraction,
+};
+
+/// A popup with one text box for typing a guess of `length` letters.
+pub fn guess_modal(custom_id: impl Into<String>, length: usize) -> CreateModal {
+    let input = CreateInputText::new(InputTextStyle::Short, "guess", "word")
+        .placeholder(format!("a {length}-letter word"))
+        .min_length(length as u16)
+        .max_length(length as u16)
+        .required(true);
+
+    CreateModal::new(custom_id, "guess").components(vec![CreateActionRow::InputText(input)])
+}
+
+/// What was typed into the guess popup.
+pub fn guess_input(interaction: &ModalInteraction) -> Option<String> {
+    interaction
+        .data
+        .components
+        .iter()
+        .flat_map(|row| &row.components)
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) => input.value.clone(),
+            _ => None,
+        })
+        .map(|word| word.trim().to_lowercase())
+}
@@ -1,24 +1,70 @@
 use std::borrow::Cow;
 
 use chrono::Utc;
+use poise::serenity_prelude::{Mentionable, UserId};
+use rand::seq::IteratorRandom;
 use serde::{Deserialize, Serialize};
 
 use crate::framework::data::UtcDateTime;
 
+use super::{absurdle::Absurdle, words_list};
+
 #[derive(Debug, Serialize, Clone)]
-pub enum Puzzle {
-    Random(#[serde(serialize_with = "kwordle::Word::serialize_as_str")] kwordle::Word<5>),
-    Daily(DailyPuzzle),
+pub enum Puzzle<const N: usize = 5> {
+    Random(#[serde(serialize_with = "kwordle::Word::serialize_as_str")] kwordle::Word<N>),
+    Daily(DailyPuzzle<N>),
+    /// A past daily, played for practice after it expired.
+    Archive(DailyPuzzle<N>),
+    /// Several random words, guessed at the same time.
+    Multi(#[serde(serialize_with = "serialize_words")] Vec<kwordle::Word<N>>),
+    /// A puzzle that dodges every guess for as long as it can.
+    Absurdle(#[serde(serialize_with = "serialize_absurdle")] Absurdle<N>),
+    /// A word someone picked for someone else to guess.
+    Challenge(ChallengePuzzle<N>),
+}
+
+fn serialize_words<S: serde::Serializer, const N: usize>(
+    words: &[kwordle::Word<N>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(words.iter().map(ToString::to_string))
 }
 
-impl Puzzle {
-    pub fn random(words: &kwordle::WordsList<5>) -> Self {
+/// An absurdle only has an answer once it's down to one.
+fn serialize_absurdle<S: serde::Serializer, const N: usize>(
+    absurdle: &Absurdle<N>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    absurdle
+        .is_fixed()
+        .then(|| absurdle.answer().to_string())
+        .serialize(serializer)
+}
+
+impl<const N: usize> Puzzle<N> {
+    pub fn random(words: &kwordle::WordsList<N>) -> Self {
         let answer = words.answers.random();
 
         Self::Random(answer)
     }
 
-    pub fn guess(&self, word: &kwordle::Word<5>) -> kwordle::Guess<5> {
+    /// Picks `count` different random words.
+    /// Gives nothing if the list doesn't have that many answers.
+    pub fn multi(words: &kwordle::WordsList<N>, count: usize) -> Option<Self> {
+        let answers: Vec<kwordle::Word<N>> = words
+            .answers
+            .iter()
+            .copied()
+            .choose_multiple(&mut rand::thread_rng(), count);
+
+        (answers.len() == count).then_some(Self::Multi(answers))
+    }
+
+    pub fn absurdle(words: &kwordle::WordsList<N>) -> Self {
+        Self::Absurdle(Absurdle::new(words))
+    }
+
+    pub fn guess(&self, word: &kwordle::Word<N>) -> kwordle::Guess<N> {
         self.answer().guess(*word)
     }
 
@@ -31,27 +77,173 @@ impl Puzzle {
         matches!(self, Self::Random(..))
     }
 
-    pub fn answer(&self) -> &kwordle::Word<5> {
+    pub fn is_multi(&self) -> bool {
+        matches!(self, Self::Multi(..))
+    }
+
+    pub fn is_absurdle(&self) -> bool {
+        matches!(self, Self::Absurdle(..))
+    }
+
+    pub fn is_challenge(&self) -> bool {
+        matches!(self, Self::Challenge(..))
+    }
+
+    /// The answer, or the first one for a puzzle with several.
+    /// An absurdle's answer can still change until it's the only one left.
+    pub fn answer(&self) -> &kwordle::Word<N> {
         match self {
-            Self::Random(answer) => answer,
-            Self::Daily(daily) => &daily.answer,
+            Self::Absurdle(absurdle) => absurdle.answer(),
+            _ => &self.answers()[0],
+        }
+    }
+
+    /// Every answer that's been decided on.
+    pub fn answers(&self) -> &[kwordle::Word<N>] {
+        match self {
+            Self::Random(answer) => std::slice::from_ref(answer),
+            Self::Daily(daily) | Self::Archive(daily) => std::slice::from_ref(&daily.answer),
+            Self::Challenge(challenge) => std::slice::from_ref(&challenge.answer),
+            Self::Multi(answers) => answers,
+            Self::Absurdle(absurdle) if absurdle.is_fixed() => {
+                std::slice::from_ref(absurdle.answer())
+            }
+            Self::Absurdle(_) => &[],
+        }
+    }
+
+    /// Says what the answer was, once the game is over.
+    pub fn reveal(&self) -> String {
+        if let Self::Absurdle(absurdle) = self
+            && !absurdle.is_fixed()
+        {
+            return format!(
+                "the word could have been any of {count} words, like {answer}",
+                count = absurdle.remaining(),
+                answer = absurdle.answer()
+            );
+        }
+
+        let answers: Vec<String> = self.answers().iter().map(ToString::to_string).collect();
+
+        match answers.as_slice() {
+            [answer] => format!("the word was: {answer}"),
+            answers => format!("the words were: {}", answers.join(", ")),
         }
     }
 
     pub fn number(&self) -> Option<u32> {
         match self {
-            Self::Random(_) => None,
+            Self::Random(_)
+            | Self::Archive(_)
+            | Self::Multi(_)
+            | Self::Absurdle(_)
+            | Self::Challenge(_) => None,
             Self::Daily(daily) => Some(daily.number),
         }
     }
 
+    /// A code to play the same random puzzle again.
+    pub fn share_code(&self) -> Option<String> {
+        match self {
+            Self::Random(answer) => super::share_code::encode(&answer.to_string()),
+            Self::Daily(_)
+            | Self::Archive(_)
+            | Self::Multi(_)
+            | Self::Absurdle(_)
+            | Self::Challenge(_) => None,
+        }
+    }
+
+    pub fn archive_number(&self) -> Option<u32> {
+        match self {
+            Self::Random(_)
+            | Self::Daily(_)
+            | Self::Multi(_)
+            | Self::Absurdle(_)
+            | Self::Challenge(_) => None,
+            Self::Archive(daily) => Some(daily.number),
+        }
+    }
+
+    pub fn challenge_number(&self) -> Option<u32> {
+        match self {
+            Self::Challenge(challenge) => Some(challenge.number),
+            _ => None,
+        }
+    }
+
     pub fn title(&self) -> Cow<str> {
         match self {
-            Self::Random(..) => "random wordle".into(),
+            Self::Random(..) if N == 5 => "random wordle".into(),
+            Self::Random(..) => format!("random {N}-letter wordle").into(),
             Self::Daily(DailyPuzzle { number, .. }) => format!("daily wordle {number}").into(),
+            Self::Archive(DailyPuzzle { number, .. }) => format!("archived wordle {number}").into(),
+            Self::Multi(answers) => match answers.len() {
+                2 => "dordle".into(),
+                4 => "quordle".into(),
+                count => format!("{count}-board wordle").into(),
+            },
+            Self::Absurdle(..) if N == 5 => "absurdle".into(),
+            Self::Absurdle(..) => format!("{N}-letter absurdle").into(),
+            Self::Challenge(ChallengePuzzle {
+                number, challenger, ..
+            }) => format!(
+                "wordle challenge {number} from {challenger}",
+                challenger = challenger.mention()
+            )
+            .into(),
         }
     }
 
+    /// What to keep to pick the puzzle back up after a restart.
+    pub fn save(&self) -> SavedPuzzle {
+        match self {
+            Self::Random(answer) => SavedPuzzle::Random {
+                answer: answer.to_string(),
+            },
+            Self::Daily(daily) => SavedPuzzle::Daily(daily.clone().into_partial()),
+            Self::Archive(daily) => SavedPuzzle::Archive(daily.clone().into_partial()),
+            Self::Multi(answers) => SavedPuzzle::Multi {
+                answers: answers.iter().map(ToString::to_string).collect(),
+            },
+            // replaying the guesses narrows it down the same way again
+            Self::Absurdle(_) => SavedPuzzle::Absurdle,
+            Self::Challenge(challenge) => SavedPuzzle::Challenge {
+                number: challenge.number,
+                challenger: challenge.challenger,
+                answer: challenge.answer.to_string(),
+            },
+        }
+    }
+
+    /// Gives nothing if a saved answer isn't a word of length `N`.
+    pub fn restore(saved: &SavedPuzzle, words: &kwordle::WordsList<N>) -> Option<Self> {
+        let puzzle = match saved {
+            SavedPuzzle::Random { answer } => Self::Random(words_list::saved_word(answer)?),
+            SavedPuzzle::Daily(daily) => Self::Daily(DailyPuzzle::from_partial(daily.clone())?),
+            SavedPuzzle::Archive(daily) => Self::Archive(DailyPuzzle::from_partial(daily.clone())?),
+            SavedPuzzle::Multi { answers } => Self::Multi(
+                answers
+                    .iter()
+                    .map(|answer| words_list::saved_word(answer))
+                    .collect::<Option<_>>()?,
+            ),
+            SavedPuzzle::Absurdle => Self::absurdle(words),
+            SavedPuzzle::Challenge {
+                number,
+                challenger,
+                answer,
+            } => Self::Challenge(ChallengePuzzle::new(
+                *number,
+                *challenger,
+                words_list::saved_word(answer)?,
+            )),
+        };
+
+        Some(puzzle)
+    }
+
     /*     pub fn from_partial(partial: PartialPuzzle, list: &kwordle::WordsList<5>) -> Option<Self> {
         match partial {
             PartialPuzzle::Random(string) => {
@@ -65,17 +257,17 @@ impl Puzzle {
 }
 
 #[derive(Debug, Serialize, Clone)]
-pub struct DailyPuzzle {
+pub struct DailyPuzzle<const N: usize = 5> {
     pub number: u32,
 
     #[serde(serialize_with = "kwordle::Word::serialize_as_str")]
-    answer: kwordle::Word<5>,
+    answer: kwordle::Word<N>,
 
     pub started: UtcDateTime,
 }
 
-impl DailyPuzzle {
-    pub fn new(number: u32, answer: kwordle::Word<5>) -> Self {
+impl<const N: usize> DailyPuzzle<N> {
+    pub fn new(number: u32, answer: kwordle::Word<N>) -> Self {
         Self {
             number,
             answer,
@@ -83,14 +275,20 @@ impl DailyPuzzle {
         }
     }
 
-    pub fn from_partial(partial: PartialDailyPuzzle, list: &kwordle::WordsList) -> Option<Self> {
+    /// Gives nothing if the saved answer isn't a word of length `N`.
+    /// It doesn't have to be in the current words list.
+    pub fn from_partial(partial: PartialDailyPuzzle) -> Option<Self> {
         Some(Self {
             number: partial.number,
-            answer: kwordle::Word::from_str(list, &partial.answer).ok()?,
+            answer: words_list::saved_word(&partial.answer)?,
             started: partial.started,
         })
     }
 
+    pub fn answer(&self) -> &kwordle::Word<N> {
+        &self.answer
+    }
+
     pub fn into_partial(self) -> PartialDailyPuzzle {
         PartialDailyPuzzle {
             number: self.number,
@@ -109,14 +307,111 @@ pub struct PartialDailyPuzzle {
     pub started: UtcDateTime,
 }
 
-impl From<DailyPuzzle> for Puzzle {
-    fn from(value: DailyPuzzle) -> Self {
+#[derive(Debug, Serialize, Clone)]
+pub struct ChallengePuzzle<const N: usize = 5> {
+    pub number: u32,
+    pub challenger: UserId,
+
+    #[serde(serialize_with = "kwordle::Word::serialize_as_str")]
+    answer: kwordle::Word<N>,
+}
+
+impl<const N: usize> ChallengePuzzle<N> {
+    pub fn new(number: u32, challenger: UserId, answer: kwordle::Word<N>) -> Self {
+        Self {
+            number,
+            challenger,
+            answer,
+        }
+    }
+}
+
+impl<const N: usize> From<DailyPuzzle<N>> for Puzzle<N> {
+    fn from(value: DailyPuzzle<N>) -> Self {
         Self::Daily(value)
     }
 }
 
+/// A puzzle as it's kept with a running game, in case the bot restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SavedPuzzle {
+    Random {
+        answer: String,
+    },
+    Daily(PartialDailyPuzzle),
+    Archive(PartialDailyPuzzle),
+    Multi {
+        answers: Vec<String>,
+    },
+    Absurdle,
+    Challenge {
+        number: u32,
+        challenger: UserId,
+        answer: String,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PartialPuzzle {
     Random(String),
     Daily(PartialDailyPuzzle),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Puzzle;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn multi_needs_enough_answers() {
+        let guesses = vec!["onion".to_owned(), "chair".to_owned(), "point".to_owned()];
+        let answers = vec!["onion".to_owned(), "chair".to_owned()];
+        let words = kwordle::WordsList::<5>::from_strs(&guesses, &answers).unwrap();
+
+        assert!(Puzzle::multi(&words, 4).is_none());
+
+        let puzzle = Puzzle::multi(&words, 2).expect("there are 2 answers");
+        let mut answers: Vec<String> = puzzle.answers().iter().map(ToString::to_string).collect();
+        answers.sort();
+
+        assert_eq!(answers, vec!["chair", "onion"]);
+    }
+
+    #[test]
+    fn restore_saved() {
+        let answers = vec!["onion".to_owned(), "chair".to_owned()];
+        let words = kwordle::WordsList::<5>::from_strs(&answers, &answers).unwrap();
+        let puzzle = Puzzle::multi(&words, 2).expect("there are 2 answers");
+
+        let restored = Puzzle::restore(&puzzle.save(), &words).expect("answers have 5 letters");
+        assert_eq!(restored.reveal(), puzzle.reveal());
+
+        let four = vec!["four".to_owned()];
+        let words = kwordle::WordsList::<4>::from_strs(&four, &four).unwrap();
+        assert!(Puzzle::restore(&puzzle.save(), &words).is_none());
+    }
+
+    #[test]
+    fn absurdle_answer_once_fixed() {
+        let answers = vec!["onion".to_owned(), "chair".to_owned()];
+        let words = kwordle::WordsList::<5>::from_strs(&answers, &answers).unwrap();
+        let puzzle = Puzzle::absurdle(&words);
+
+        assert_eq!(
+            serde_json::to_value(&puzzle).unwrap(),
+            serde_json::json!({ "Absurdle": null })
+        );
+
+        let Puzzle::Absurdle(absurdle) = &puzzle else {
+            unreachable!()
+        };
+        let guess = kwordle::Word::from_str(&words, "chair").unwrap();
+        let puzzle = Puzzle::Absurdle(absurdle.respond(&guess, &words));
+
+        assert_eq!(
+            serde_json::to_value(&puzzle).unwrap(),
+            serde_json::json!({ "Absurdle": "onion" })
+        );
+    }
+}
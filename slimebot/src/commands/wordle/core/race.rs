@@ -0,0 +1,551 @@
+use std::{fmt, time::Duration};
+
+use poise::{
+    serenity_prelude::{
+        futures::StreamExt, ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton,
+        CreateInteractionResponse, CreateInteractionResponseMessage, EditMessage,
+        ModalInteractionCollector, ReactionType, UserId,
+    },
+    CreateReply,
+};
+
+use crate::{
+    errors::CommandError, utils::serenity::component_interaction::ComponentInteractionExt, Context,
+};
+
+use super::{
+    game::{
+        self,
+        hard_mode::{self, Violation},
+        GuessError,
+    },
+    modal, AsEmoji,
+};
+
+/// How many guesses each player gets.
+const GUESSES_LIMIT: usize = 6;
+
+/// How long the lobby stays open if the host doesn't start the race.
+const LOBBY_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// How long a race can go without guesses before it's called off.
+const RACE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{n}{suffix}")
+}
+
+#[derive(Debug, Clone)]
+struct Player<const N: usize> {
+    user: UserId,
+    name: String,
+    guesses: kwordle::Guesses<N>,
+    /// Finishing position, once the player has solved it.
+    place: Option<usize>,
+}
+
+impl<const N: usize> Player<N> {
+    fn is_solved(&self) -> bool {
+        self.place.is_some()
+    }
+
+    fn is_done(&self) -> bool {
+        self.is_solved() || self.guesses.count() >= GUESSES_LIMIT
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Solved { place: usize },
+    OutOfGuesses,
+    Continue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceError {
+    NotPlaying,
+    Done,
+    HardMode(Violation),
+}
+
+impl fmt::Display for RaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPlaying => write!(f, "you're not in this race!"),
+            Self::Done => write!(f, "you're done! wait for everyone else to finish"),
+            Self::HardMode(violation) => violation.fmt(f),
+        }
+    }
+}
+
+/// Everyone's boards in a race to find the same word.
+#[derive(Debug, Clone)]
+pub struct Race<const N: usize = 5> {
+    answer: kwordle::Word<N>,
+    players: Vec<Player<N>>,
+    hard_mode: bool,
+    solved: usize,
+}
+
+impl<const N: usize> Race<N> {
+    pub fn new(
+        answer: kwordle::Word<N>,
+        players: impl IntoIterator<Item = (UserId, String)>,
+        hard_mode: bool,
+    ) -> Self {
+        Self {
+            answer,
+            players: players
+                .into_iter()
+                .map(|(user, name)| Player {
+                    user,
+                    name,
+                    guesses: kwordle::Guesses::new(Some(GUESSES_LIMIT)),
+                    place: None,
+                })
+                .collect(),
+            hard_mode,
+            solved: 0,
+        }
+    }
+
+    fn player(&self, user: UserId) -> Option<&Player<N>> {
+        self.players.iter().find(|player| player.user == user)
+    }
+
+    pub fn is_playing(&self, user: UserId) -> bool {
+        self.player(user).is_some_and(|player| !player.is_done())
+    }
+
+    pub fn guess(&mut self, user: UserId, word: kwordle::Word<N>) -> Result<Outcome, RaceError> {
+        let hard_mode = self.hard_mode;
+        let answer = self.answer;
+
+        let player = self
+            .players
+            .iter_mut()
+            .find(|player| player.user == user)
+            .ok_or(RaceError::NotPlaying)?;
+
+        if player.is_done() {
+            return Err(RaceError::Done);
+        }
+
+        if hard_mode && let Some(violation) = hard_mode::check(&player.guesses, &word) {
+            return Err(RaceError::HardMode(violation));
+        }
+
+        player.guesses.push(answer.guess(word));
+
+        if player.guesses.latest_is_correct() {
+            self.solved += 1;
+            player.place = Some(self.solved);
+            Ok(Outcome::Solved { place: self.solved })
+        } else if player.is_done() {
+            Ok(Outcome::OutOfGuesses)
+        } else {
+            Ok(Outcome::Continue)
+        }
+    }
+
+    /// A player's own board, with letters.
+    pub fn player_board(&self, user: UserId) -> Option<String> {
+        let player = self.player(user)?;
+
+        Some(format!(
+            "{count}/{GUESSES_LIMIT}\n{board}",
+            count = player.guesses.count(),
+            board = player.guesses.emoji_with_letters()
+        ))
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.players.iter().all(Player::is_done)
+    }
+
+    /// Everyone's progress, in colors only so nobody's letters are given away.
+    pub fn board(&self) -> String {
+        let mut lines = vec![format!(
+            "**wordle race!**{hard_mode} first to find the {N}-letter word wins",
+            hard_mode = if self.hard_mode { " (hard mode)" } else { "" }
+        )];
+
+        for player in &self.players {
+            let progress = match (player.place, player.guesses.latest()) {
+                (Some(place), _) => format!(
+                    "solved in {count}! ({place})",
+                    count = player.guesses.count(),
+                    place = ordinal(place)
+                ),
+                (None, Some(latest)) => format!(
+                    "{latest} {count}/{GUESSES_LIMIT}",
+                    latest = latest.as_emoji(),
+                    count = player.guesses.count()
+                ),
+                (None, None) => "no guesses yet".to_owned(),
+            };
+
+            lines.push(format!("`{name}` {progress}", name = player.name));
+        }
+
+        lines.join("\n")
+    }
+
+    /// The finishing order, once the race is over.
+    pub fn results(&self) -> String {
+        let mut players: Vec<&Player<N>> = self.players.iter().collect();
+        players.sort_by_key(|player| player.place.unwrap_or(usize::MAX));
+
+        let mut lines = vec![format!(
+            "**wordle race over!** the word was `{answer}`",
+            answer = self.answer
+        )];
+
+        for player in players {
+            let result = match player.place {
+                Some(place) => format!(
+                    "{place}. `{name}` solved in {count}",
+                    place = place,
+                    name = player.name,
+                    count = player.guesses.count()
+                ),
+                None => format!("`{name}` didn't solve it", name = player.name),
+            };
+
+            lines.push(result);
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn lobby_text(players: &[(UserId, String)]) -> String {
+    let names: Vec<String> = players
+        .iter()
+        .map(|(_, name)| format!("`{name}`"))
+        .collect();
+
+    format!(
+        "**wordle race!** press join to play, and the host can start once everyone's in\nplayers: {names}",
+        names = names.join(", ")
+    )
+}
+
+fn lobby_buttons() -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new("join")
+            .emoji(ReactionType::Unicode("🏁".to_owned()))
+            .label("join")
+            .style(ButtonStyle::Primary),
+        CreateButton::new("start")
+            .emoji(ReactionType::Unicode("▶️".to_owned()))
+            .label("start")
+            .style(ButtonStyle::Success),
+    ])]
+}
+
+fn race_buttons() -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new("guess")
+            .emoji(ReactionType::Unicode("✏️".to_owned()))
+            .label("guess")
+            .style(ButtonStyle::Primary),
+        CreateButton::new("board")
+            .emoji(ReactionType::Unicode("🔎".to_owned()))
+            .label("my board")
+            .style(ButtonStyle::Secondary),
+    ])]
+}
+
+async fn respond_ephemeral(
+    ctx: Context<'_>,
+    interaction: &ComponentInteraction,
+    content: impl Into<String>,
+) -> Result<(), CommandError> {
+    interaction.reply_ephemeral(ctx, content).await?;
+    Ok(())
+}
+
+/// Opens a lobby, then runs a race between everyone who joined.
+///
+/// Unlike other games, races aren't saved as [`super::ActiveGame`]s, so a race that's
+/// running when the bot restarts is lost, and its message keeps its old buttons.
+pub async fn run<const N: usize>(
+    ctx: Context<'_>,
+    words: &kwordle::WordsList<N>,
+    hard_mode: bool,
+) -> Result<(), CommandError> {
+    let shard = ctx.serenity_context();
+    let host = ctx.author();
+
+    let mut players = vec![(host.id, host.name.clone())];
+
+    let mut msg = ctx
+        .send(
+            CreateReply::default()
+                .content(lobby_text(&players))
+                .components(lobby_buttons()),
+        )
+        .await?
+        .into_message()
+        .await?;
+
+    let mut lobby = msg.await_component_interactions(shard).stream();
+    let lobby_timeout = tokio::time::sleep(LOBBY_TIMEOUT);
+    tokio::pin!(lobby_timeout);
+
+    loop {
+        tokio::select! {
+            Some(interaction) = lobby.next() => match interaction.data.custom_id.as_str() {
+                "join" if players.iter().any(|(user, _)| *user == interaction.user.id) => {
+                    respond_ephemeral(ctx, &interaction, "you've already joined!").await?;
+                }
+                "join" => {
+                    players.push((interaction.user.id, interaction.user.name.clone()));
+
+                    interaction
+                        .create_response(
+                            ctx,
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new().content(lobby_text(&players)),
+                            ),
+                        )
+                        .await?;
+                }
+                "start" if interaction.user.id == host.id => {
+                    interaction.acknowledge(ctx).await?;
+                    break;
+                }
+                _ => {
+                    respond_ephemeral(ctx, &interaction, "only the host can start the race!").await?;
+                }
+            },
+            () = &mut lobby_timeout => {
+                msg.edit(
+                    ctx,
+                    EditMessage::new()
+                        .content("the host didn't start the race in time!")
+                        .components(Vec::new()),
+                )
+                .await?;
+
+                return Ok(());
+            }
+        }
+    }
+
+    if players.len() < 2 {
+        msg.edit(
+            ctx,
+            EditMessage::new()
+                .content("not enough players joined the race!")
+                .components(Vec::new()),
+        )
+        .await?;
+
+        return Ok(());
+    }
+
+    let mut race = Race::new(words.answers.random(), players, hard_mode);
+
+    msg.edit(
+        ctx,
+        EditMessage::new()
+            .content(race.board())
+            .components(race_buttons()),
+    )
+    .await?;
+
+    let modal_id = format!("race_guess_{}", msg.id);
+
+    let mut buttons = msg.await_component_interactions(shard).stream();
+    let mut guesses = ModalInteractionCollector::new(shard)
+        .filter({
+            let modal_id = modal_id.clone();
+            move |interaction| interaction.data.custom_id == modal_id
+        })
+        .stream();
+
+    let idle = tokio::time::sleep(RACE_TIMEOUT);
+    tokio::pin!(idle);
+
+    loop {
+        tokio::select! {
+            Some(interaction) = buttons.next() => {
+                let user = interaction.user.id;
+
+                match interaction.data.custom_id.as_str() {
+                    "guess" if race.is_playing(user) => {
+                        interaction
+                            .create_response(
+                                ctx,
+                                CreateInteractionResponse::Modal(modal::guess_modal(&modal_id, N)),
+                            )
+                            .await?;
+                    }
+                    "guess" if race.player_board(user).is_some() => {
+                        respond_ephemeral(ctx, &interaction, RaceError::Done.to_string()).await?;
+                    }
+                    _ => match race.player_board(user) {
+                        Some(board) => respond_ephemeral(ctx, &interaction, board).await?,
+                        None => respond_ephemeral(ctx, &interaction, RaceError::NotPlaying.to_string()).await?,
+                    },
+                }
+            }
+            Some(interaction) = guesses.next() => {
+                let user = interaction.user.id;
+
+                let word = modal::guess_input(&interaction)
+                    .ok_or(GuessError::Invalid)
+                    .and_then(|input| game::check(words, &input, None));
+
+                let reply = match word.map(|word| race.guess(user, word)) {
+                    Err(err) => err.to_string(),
+                    Ok(Err(err)) => err.to_string(),
+                    Ok(Ok(outcome)) => {
+                        idle.as_mut().reset(tokio::time::Instant::now() + RACE_TIMEOUT);
+
+                        let board = race.player_board(user).unwrap_or_default();
+
+                        match outcome {
+                            Outcome::Solved { place } => format!("{board}\nyou solved it! you finished {}", ordinal(place)),
+                            Outcome::OutOfGuesses => format!("{board}\nout of guesses!"),
+                            Outcome::Continue => board,
+                        }
+                    }
+                };
+
+                interaction
+                    .create_response(
+                        ctx,
+                        CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new().content(reply).ephemeral(true),
+                        ),
+                    )
+                    .await?;
+
+                msg.edit(ctx, EditMessage::new().content(race.board())).await?;
+
+                if race.is_over() {
+                    break;
+                }
+            }
+            () = &mut idle => break,
+        }
+    }
+
+    msg.edit(
+        ctx,
+        EditMessage::new()
+            .content(race.results())
+            .components(Vec::new()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ordinal, Outcome, Race, RaceError, GUESSES_LIMIT};
+    use poise::serenity_prelude::UserId;
+    use pretty_assertions::assert_eq;
+
+    fn word(word: &str) -> kwordle::Word<5> {
+        kwordle::Word::from_str(&kwordle::classic::words_list(), word).expect("should be valid")
+    }
+
+    fn race(hard_mode: bool) -> Race {
+        Race::new(
+            word("onion"),
+            [
+                (UserId::new(1), "one".to_owned()),
+                (UserId::new(2), "two".to_owned()),
+            ],
+            hard_mode,
+        )
+    }
+
+    #[test]
+    fn ordinals() {
+        let ordinals: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101].map(ordinal).to_vec();
+
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "101st"]
+        );
+    }
+
+    #[test]
+    fn finishing_order() {
+        let mut race = race(false);
+
+        assert_eq!(
+            race.guess(UserId::new(2), word("chair")),
+            Ok(Outcome::Continue)
+        );
+        assert_eq!(
+            race.guess(UserId::new(2), word("onion")),
+            Ok(Outcome::Solved { place: 1 })
+        );
+        assert!(!race.is_over());
+
+        assert_eq!(
+            race.guess(UserId::new(1), word("onion")),
+            Ok(Outcome::Solved { place: 2 })
+        );
+        assert!(race.is_over());
+
+        let results = race.results();
+        let mut lines = results.lines().skip(1);
+
+        assert_eq!(lines.next(), Some("1. `two` solved in 2"));
+        assert_eq!(lines.next(), Some("2. `one` solved in 1"));
+    }
+
+    #[test]
+    fn out_of_guesses() {
+        let mut race = race(false);
+
+        for _ in 1..GUESSES_LIMIT {
+            assert_eq!(
+                race.guess(UserId::new(1), word("chair")),
+                Ok(Outcome::Continue)
+            );
+        }
+
+        assert_eq!(
+            race.guess(UserId::new(1), word("chair")),
+            Ok(Outcome::OutOfGuesses)
+        );
+        assert_eq!(
+            race.guess(UserId::new(1), word("onion")),
+            Err(RaceError::Done)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut race = race(true);
+
+        assert_eq!(
+            race.guess(UserId::new(3), word("onion")),
+            Err(RaceError::NotPlaying)
+        );
+
+        race.guess(UserId::new(1), word("chair")).unwrap();
+
+        assert!(matches!(
+            race.guess(UserId::new(1), word("crane")),
+            Err(RaceError::HardMode(_))
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+//! Draws boards as PNG images, which look the same on every client,
+//! unlike emoji. The font is bundled so nothing has to be installed.
+
+use std::{collections::HashMap, io::Cursor};
+
+use ab_glyph::{Font, FontRef, PxScale};
+use image::{ImageFormat, Rgba, RgbaImage};
+use kwordle::LetterState;
+
+use super::game::hard_mode::letter_char;
+
+const FONT: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../static/DejaVuSans-Bold.ttf"
+));
+
+const TILE: u32 = 60;
+const GAP: u32 = 6;
+const KEY_WIDTH: u32 = 28;
+const KEY_HEIGHT: u32 = 38;
+const KEY_GAP: u32 = 4;
+const PADDING: u32 = 12;
+
+/// The board's height in rows when it isn't full yet.
+const MIN_ROWS: usize = 6;
+
+const KEYBOARD: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const BACKGROUND: Rgba<u8> = Rgba([18, 18, 19, 255]);
+const BORDER: Rgba<u8> = Rgba([58, 58, 60, 255]);
+const TEXT: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const UNUSED_KEY: Rgba<u8> = Rgba([129, 131, 132, 255]);
+
+fn color(state: LetterState) -> Rgba<u8> {
+    match state {
+        LetterState::Correct => Rgba([83, 141, 78, 255]),
+        LetterState::WrongPlace => Rgba([181, 159, 59, 255]),
+        LetterState::NotPresent => Rgba([58, 58, 60, 255]),
+    }
+}
+
+fn rank(state: LetterState) -> u8 {
+    match state {
+        LetterState::NotPresent => 0,
+        LetterState::WrongPlace => 1,
+        LetterState::Correct => 2,
+    }
+}
+
+/// The best state each letter has been revealed with.
+fn keyboard(rows: &[Vec<(char, LetterState)>]) -> HashMap<char, LetterState> {
+    let mut keys: HashMap<char, LetterState> = HashMap::new();
+
+    for &(letter, state) in rows.iter().flatten() {
+        keys.entry(letter)
+            .and_modify(|best| {
+                if rank(state) > rank(*best) {
+                    *best = state;
+                }
+            })
+            .or_insert(state);
+    }
+
+    keys
+}
+
+fn fill(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..y + height {
+        for px in x..x + width {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn outline(image: &mut RgbaImage, x: u32, y: u32, size: u32, color: Rgba<u8>) {
+    const THICKNESS: u32 = 2;
+
+    fill(image, x, y, size, THICKNESS, color);
+    fill(image, x, y + size - THICKNESS, size, THICKNESS, color);
+    fill(image, x, y, THICKNESS, size, color);
+    fill(image, x + size - THICKNESS, y, THICKNESS, size, color);
+}
+
+/// Draws a letter in the middle of a box, blended over what's already there.
+fn letter(
+    image: &mut RgbaImage,
+    font: &FontRef,
+    letter: char,
+    (x, y, width, height): (u32, u32, u32, u32),
+    size: f32,
+) {
+    let glyph = font
+        .glyph_id(letter.to_ascii_uppercase())
+        .with_scale(PxScale::from(size));
+
+    let Some(outlined) = font.outline_glyph(glyph) else {
+        return;
+    };
+
+    let bounds = outlined.px_bounds();
+    let left = x as f32 + (width as f32 - bounds.width()) / 2.0;
+    let top = y as f32 + (height as f32 - bounds.height()) / 2.0;
+
+    outlined.draw(|gx, gy, coverage| {
+        let (px, py) = ((left as u32) + gx, (top as u32) + gy);
+
+        if px < image.width() && py < image.height() {
+            let pixel = image.get_pixel_mut(px, py);
+
+            for channel in 0..3 {
+                pixel.0[channel] = (f32::from(pixel.0[channel]) * (1.0 - coverage)
+                    + f32::from(TEXT.0[channel]) * coverage)
+                    as u8;
+            }
+        }
+    });
+}
+
+fn draw(rows: &[Vec<(char, LetterState)>], length: usize, limit: Option<usize>) -> RgbaImage {
+    let font = FontRef::try_from_slice(FONT).expect("bundled font should be valid");
+
+    let height_rows = rows.len().max(limit.unwrap_or(MIN_ROWS)) as u32;
+    let length = length as u32;
+
+    let board_width = length * TILE + (length - 1) * GAP;
+    let keyboard_width = KEYBOARD[0].len() as u32 * (KEY_WIDTH + KEY_GAP) - KEY_GAP;
+    let width = board_width.max(keyboard_width) + 2 * PADDING;
+
+    let board_height = height_rows * (TILE + GAP) - GAP;
+    let keyboard_height = KEYBOARD.len() as u32 * (KEY_HEIGHT + KEY_GAP) - KEY_GAP;
+    let height = board_height + keyboard_height + 3 * PADDING;
+
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+    let board_left = (width - board_width) / 2;
+
+    for row in 0..height_rows {
+        for column in 0..length {
+            let x = board_left + column * (TILE + GAP);
+            let y = PADDING + row * (TILE + GAP);
+
+            match rows
+                .get(row as usize)
+                .and_then(|row| row.get(column as usize))
+            {
+                Some(&(c, state)) => {
+                    fill(&mut image, x, y, TILE, TILE, color(state));
+                    letter(&mut image, &font, c, (x, y, TILE, TILE), 36.0);
+                }
+                None => outline(&mut image, x, y, TILE, BORDER),
+            }
+        }
+    }
+
+    let keys = keyboard(rows);
+    let keyboard_top = PADDING * 2 + board_height;
+
+    for (row, letters) in KEYBOARD.iter().enumerate() {
+        let row_width = letters.len() as u32 * (KEY_WIDTH + KEY_GAP) - KEY_GAP;
+        let left = (width - row_width) / 2;
+        let y = keyboard_top + row as u32 * (KEY_HEIGHT + KEY_GAP);
+
+        for (column, c) in letters.chars().enumerate() {
+            let x = left + column as u32 * (KEY_WIDTH + KEY_GAP);
+            let color = keys.get(&c).copied().map_or(UNUSED_KEY, color);
+
+            fill(&mut image, x, y, KEY_WIDTH, KEY_HEIGHT, color);
+            letter(&mut image, &font, c, (x, y, KEY_WIDTH, KEY_HEIGHT), 18.0);
+        }
+    }
+
+    image
+}
+
+fn encode(image: &RgbaImage) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .expect("encoding to memory shouldn't fail");
+
+    bytes
+}
+
+/// Draws the guesses and the keyboard, as a PNG file.
+pub fn board<const N: usize>(guesses: &kwordle::Guesses<N>) -> Vec<u8> {
+    let rows: Vec<Vec<(char, LetterState)>> = guesses
+        .iter()
+        .map(|guess| {
+            guess
+                .into_iter()
+                .map(|(letter, state)| (letter_char(letter), state))
+                .collect()
+        })
+        .collect();
+
+    encode(&draw(&rows, N, guesses.max()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{board, draw, keyboard, GAP, MIN_ROWS, PADDING, TILE};
+    use kwordle::LetterState;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keyboard_keeps_best() {
+        let rows = vec![
+            vec![
+                ('a', LetterState::NotPresent),
+                ('b', LetterState::WrongPlace),
+            ],
+            vec![('a', LetterState::Correct), ('b', LetterState::NotPresent)],
+        ];
+
+        let keys = keyboard(&rows);
+
+        assert!(matches!(keys[&'a'], LetterState::Correct));
+        assert!(matches!(keys[&'b'], LetterState::WrongPlace));
+        assert!(!keys.contains_key(&'c'));
+    }
+
+    #[test]
+    fn grows_with_guesses() {
+        let row = vec![('a', LetterState::Correct); 5];
+
+        let short = draw(&vec![row.clone(); 2], 5, None);
+        let long = draw(&vec![row; MIN_ROWS + 2], 5, None);
+
+        assert_eq!(long.height() - short.height(), 2 * (TILE + GAP));
+        assert!(short.width() >= 5 * TILE + 2 * PADDING);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let mut guesses = kwordle::Guesses::new(None);
+        guesses.push(answer.guess(kwordle::Word::from_str(&words, "chair").unwrap()));
+
+        let png = board(&guesses);
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
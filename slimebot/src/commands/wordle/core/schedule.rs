@@ -0,0 +1,119 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveTime, Utc};
+
+use crate::framework::config::WordleConfig;
+
+/// When a new daily wordle starts each day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    time: NaiveTime,
+    offset: FixedOffset,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            time: NaiveTime::MIN,
+            offset: FixedOffset::east_opt(0).expect("0 is a valid offset"),
+        }
+    }
+}
+
+impl Schedule {
+    pub fn new(time: NaiveTime, offset: FixedOffset) -> Self {
+        Self { time, offset }
+    }
+
+    pub fn from_config(config: &WordleConfig) -> Self {
+        Self::new(
+            config.daily_time.unwrap_or_default(),
+            config.utc_offset.unwrap_or(Self::default().offset),
+        )
+    }
+
+    /// The most recent rollover at or before `now`.
+    pub fn period_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let local = now.with_timezone(&self.offset);
+
+        let start = local.date_naive().and_time(self.time)
+            - Duration::seconds(self.offset.local_minus_utc().into());
+        let start = DateTime::from_naive_utc_and_offset(start, Utc);
+
+        if start > now {
+            start - Duration::days(1)
+        } else {
+            start
+        }
+    }
+
+    pub fn next_rollover(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.period_start(now) + Duration::days(1)
+    }
+
+    /// How many rollovers have happened between `started` and `now`.
+    pub fn periods_since(&self, started: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+        (self.period_start(now) - self.period_start(started)).num_days()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveTime, TimeZone, Utc};
+    use pretty_assertions::assert_eq;
+
+    use super::Schedule;
+
+    fn schedule(hour: u32, offset_hours: i32) -> Schedule {
+        Schedule::new(
+            NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            FixedOffset::east_opt(offset_hours * 3600).unwrap(),
+        )
+    }
+
+    #[test]
+    fn period_start_utc() {
+        let schedule = Schedule::default();
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 15, 30, 0).unwrap();
+
+        assert_eq!(
+            schedule.period_start(now),
+            Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            schedule.next_rollover(now),
+            Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn period_start_offset() {
+        // 9am at UTC-5 is 2pm UTC
+        let schedule = schedule(9, -5);
+
+        assert_eq!(
+            schedule.period_start(Utc.with_ymd_and_hms(2024, 3, 10, 13, 59, 0).unwrap()),
+            Utc.with_ymd_and_hms(2024, 3, 9, 14, 0, 0).unwrap()
+        );
+        assert_eq!(
+            schedule.period_start(Utc.with_ymd_and_hms(2024, 3, 10, 14, 0, 0).unwrap()),
+            Utc.with_ymd_and_hms(2024, 3, 10, 14, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn periods_since() {
+        let schedule = schedule(9, -5);
+        let started = Utc.with_ymd_and_hms(2024, 3, 10, 14, 0, 5).unwrap();
+
+        let periods = |day, hour| {
+            schedule.periods_since(
+                started,
+                Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap(),
+            )
+        };
+
+        assert_eq!(periods(11, 13), 0);
+        assert_eq!(periods(11, 14), 1);
+        assert_eq!(periods(12, 13), 1);
+        assert_eq!(periods(12, 14), 2);
+    }
+}
@@ -0,0 +1,136 @@
+//! Short codes for random puzzles, so the same word can be played again
+//! without the code giving it away.
+//!
+//! A word is read as a base 26 number, scrambled by multiplying it with a
+//! constant that's coprime to 26, and written in base 36 after its length.
+
+const MULTIPLIER: u64 = 7_436_917;
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+fn modulus(length: usize) -> u64 {
+    26_u64.pow(length as u32)
+}
+
+/// The inverse of `MULTIPLIER`, modulo `modulus`.
+fn inverse(modulus: u64) -> u64 {
+    let (mut old_r, mut r) = (i128::from(MULTIPLIER), i128::from(modulus));
+    let (mut old_s, mut s) = (1_i128, 0_i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    old_s.rem_euclid(i128::from(modulus)) as u64
+}
+
+fn scramble(value: u64, multiplier: u64, modulus: u64) -> u64 {
+    (u128::from(value) * u128::from(multiplier) % u128::from(modulus)) as u64
+}
+
+/// Makes the code for a word of 4 to 7 lowercase letters.
+pub fn encode(word: &str) -> Option<String> {
+    let length = word.len();
+
+    if !(4..=7).contains(&length) || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+
+    let value = word
+        .bytes()
+        .fold(0, |value, b| value * 26 + u64::from(b - b'a'));
+
+    let mut scrambled = scramble(value, MULTIPLIER, modulus(length));
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(DIGITS[(scrambled % 36) as usize]);
+        scrambled /= 36;
+
+        if scrambled == 0 {
+            break;
+        }
+    }
+
+    digits.reverse();
+
+    Some(format!(
+        "{length}{digits}",
+        digits = String::from_utf8(digits).expect("digits are ascii")
+    ))
+}
+
+/// Gets the word back from a code.
+pub fn decode(code: &str) -> Option<String> {
+    let code = code.trim().to_ascii_lowercase();
+    let mut chars = code.chars();
+
+    let length = chars.next()?.to_digit(10)? as usize;
+
+    if !(4..=7).contains(&length) {
+        return None;
+    }
+
+    let mut scrambled: u64 = 0;
+
+    for c in chars {
+        scrambled = scrambled
+            .checked_mul(36)?
+            .checked_add(u64::from(c.to_digit(36)?))?;
+    }
+
+    let modulus = modulus(length);
+
+    if scrambled >= modulus {
+        return None;
+    }
+
+    let mut value = scramble(scrambled, inverse(modulus), modulus);
+    let mut word = vec![b'a'; length];
+
+    for b in word.iter_mut().rev() {
+        *b += (value % 26) as u8;
+        value /= 26;
+    }
+
+    String::from_utf8(word).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, MULTIPLIER};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn multiplier_coprime() {
+        assert!(MULTIPLIER % 2 != 0 && MULTIPLIER % 13 != 0);
+    }
+
+    #[test]
+    fn round_trip() {
+        for word in ["onion", "mummy", "aaaa", "zzzzzzz", "wordles"] {
+            let code = encode(word).expect("should be encodable");
+            assert_eq!(decode(&code).as_deref(), Some(word));
+        }
+    }
+
+    #[test]
+    fn hides_word() {
+        let code = encode("onion").unwrap();
+
+        assert!(!code.contains("onion"));
+        assert_ne!(encode("onion"), encode("onioo"));
+        assert_eq!(decode(&code.to_ascii_uppercase()).as_deref(), Some("onion"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(encode("abc"), None);
+        assert_eq!(encode("Onion"), None);
+        assert_eq!(decode(""), None);
+        assert_eq!(decode("3abc"), None);
+        assert_eq!(decode("5!!"), None);
+        assert_eq!(decode("5zzzzzzzz"), None);
+    }
+}
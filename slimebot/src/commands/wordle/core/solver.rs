@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use super::game::hard_mode::letter_char;
+
+/// The colors of a guess, packed into one number: each letter is a base 3 digit
+/// that's 0 for not present, 1 for wrong place and 2 for correct.
+pub type Pattern = u32;
+
+const NOT_PRESENT: Pattern = 0;
+const WRONG_PLACE: Pattern = 1;
+const CORRECT: Pattern = 2;
+
+fn pack(digits: impl IntoIterator<Item = Pattern>) -> Pattern {
+    digits
+        .into_iter()
+        .enumerate()
+        .map(|(index, digit)| digit * 3_u32.pow(index as u32))
+        .sum()
+}
+
+/// Colors a guess against an answer, the same way a wordle game does.
+pub fn pattern(answer: &[char], guess: &[char]) -> Pattern {
+    let mut digits = vec![NOT_PRESENT; guess.len()];
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+
+    for (index, (&a, &g)) in answer.iter().zip(guess).enumerate() {
+        if a == g {
+            digits[index] = CORRECT;
+        } else {
+            *unmatched.entry(a).or_default() += 1;
+        }
+    }
+
+    for (index, g) in guess.iter().enumerate() {
+        if digits[index] == CORRECT {
+            continue;
+        }
+
+        if let Some(count) = unmatched.get_mut(g)
+            && *count > 0
+        {
+            *count -= 1;
+            digits[index] = WRONG_PLACE;
+        }
+    }
+
+    pack(digits)
+}
+
+/// A guess made in a game, as its letters and the pattern it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revealed {
+    word: Vec<char>,
+    pattern: Pattern,
+}
+
+impl Revealed {
+    #[cfg(test)]
+    pub fn new(word: &str, pattern: Pattern) -> Self {
+        Self {
+            word: word.chars().collect(),
+            pattern,
+        }
+    }
+
+    pub fn from_guess<const N: usize>(guess: &kwordle::Guess<N>) -> Self {
+        let mut word = Vec::with_capacity(N);
+        let mut digits = Vec::with_capacity(N);
+
+        for (letter, state) in guess {
+            word.push(letter_char(letter));
+            digits.push(match state {
+                kwordle::LetterState::Correct => CORRECT,
+                kwordle::LetterState::WrongPlace => WRONG_PLACE,
+                kwordle::LetterState::NotPresent => NOT_PRESENT,
+            });
+        }
+
+        Self {
+            word,
+            pattern: pack(digits),
+        }
+    }
+
+    pub fn word(&self) -> &[char] {
+        &self.word
+    }
+
+    pub fn pattern(&self) -> Pattern {
+        self.pattern
+    }
+
+    fn allows(&self, answer: &[char]) -> bool {
+        pattern(answer, &self.word) == self.pattern
+    }
+}
+
+/// The answers that could still be right, given every guess so far.
+pub fn candidates<'a>(
+    revealed: &[Revealed],
+    answers: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    answers
+        .into_iter()
+        .filter(|answer| {
+            let answer: Vec<char> = answer.chars().collect();
+            revealed.iter().all(|guess| guess.allows(&answer))
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Splits up the candidates by the pattern they'd give the guess.
+pub fn groups(guess: &[char], candidates: &[Vec<char>]) -> HashMap<Pattern, usize> {
+    let mut groups: HashMap<Pattern, usize> = HashMap::new();
+
+    for answer in candidates {
+        *groups.entry(pattern(answer, guess)).or_default() += 1;
+    }
+
+    groups
+}
+
+/// The sum of the squared group sizes, which is proportional to the expected
+/// number of candidates left after the guess.
+fn spread(guess: &[char], candidates: &[Vec<char>]) -> usize {
+    groups(guess, candidates)
+        .values()
+        .map(|size| size * size)
+        .sum()
+}
+
+/// How many candidates are expected to be left after the guess.
+pub fn expected_remaining(guess: &[char], candidates: &[Vec<char>]) -> f64 {
+    if candidates.is_empty() {
+        0.0
+    } else {
+        spread(guess, candidates) as f64 / candidates.len() as f64
+    }
+}
+
+/// The index of the candidate that leaves the fewest candidates on average once it's guessed.
+pub fn best_index(candidates: &[Vec<char>]) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, guess)| spread(guess, candidates))
+        .map(|(index, _)| index)
+}
+
+/// Picks the candidate that leaves the fewest candidates on average once it's guessed.
+///
+/// Only candidates are considered as guesses, so the suggestion could always be the answer.
+pub fn best_guess(candidates: &[String]) -> Option<&str> {
+    let words: Vec<Vec<char>> = candidates
+        .iter()
+        .map(|word| word.chars().collect())
+        .collect();
+
+    best_index(&words).map(|index| candidates[index].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_guess, candidates, pack, pattern, Revealed};
+    use pretty_assertions::assert_eq;
+
+    fn chars(word: &str) -> Vec<char> {
+        word.chars().collect()
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(pattern(&chars("onion"), &chars("onion")), pack([2; 5]));
+        assert_eq!(
+            pattern(&chars("onion"), &chars("chair")),
+            pack([0, 0, 0, 1, 0])
+        );
+        // only one `o` is left over for the second `o` in the guess
+        assert_eq!(
+            pattern(&chars("onion"), &chars("robot")),
+            pack([0, 1, 0, 2, 0])
+        );
+    }
+
+    #[test]
+    fn filters_candidates() {
+        let revealed = [Revealed::new("chair", pack([0, 0, 0, 1, 0]))];
+        let answers = ["onion", "point", "crane", "noisy"];
+
+        assert_eq!(
+            candidates(&revealed, answers),
+            vec!["onion", "point", "noisy"]
+        );
+    }
+
+    #[test]
+    fn suggests_splitting_guess() {
+        let candidates = ["bills", "fills", "hills", "pills", "flips"].map(str::to_owned);
+
+        // `flips` splits the other words up the most
+        assert_eq!(best_guess(&candidates), Some("flips"));
+        assert_eq!(best_guess(&[]), None);
+    }
+}
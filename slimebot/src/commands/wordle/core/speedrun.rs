@@ -0,0 +1,199 @@
+use chrono::Utc;
+use mongodb::{bson::doc, Collection, Database};
+use poise::serenity_prelude::{futures::TryStreamExt, GuildId, UserId};
+use serde::{Deserialize, Serialize};
+
+use crate::framework::data::UtcDateTime;
+
+use super::DbResult;
+
+/// When discord started counting, in milliseconds since the unix epoch.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// When discord says something with this id was sent, in milliseconds since the unix epoch.
+/// Ids are handed out by discord, so this doesn't depend on how quickly the bot heard about it.
+pub fn sent_at(id: u64) -> i64 {
+    ((id >> 22) + DISCORD_EPOCH) as i64
+}
+
+/// Formats a time like `1:05.250`.
+pub fn format_time(millis: i64) -> String {
+    let millis = millis.max(0);
+
+    format!(
+        "{minutes}:{seconds:02}.{millis:03}",
+        minutes = millis / 60_000,
+        seconds = millis / 1000 % 60,
+        millis = millis % 1000
+    )
+}
+
+/// Times a game from its first guess to the one that solves it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Timer {
+    started: Option<i64>,
+    stopped: Option<i64>,
+}
+
+impl Timer {
+    /// Counts a guess sent at `at`, in milliseconds since the unix epoch.
+    pub fn record(&mut self, at: i64, solved: bool) {
+        self.started.get_or_insert(at);
+
+        if solved {
+            self.stopped = Some(at);
+        }
+    }
+
+    /// How long the game took, once it's solved.
+    pub fn elapsed(&self) -> Option<i64> {
+        Some(self.stopped? - self.started?)
+    }
+}
+
+impl std::fmt::Display for Timer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.started, self.elapsed()) {
+            (_, Some(elapsed)) => write!(f, "⏱️ {}", format_time(elapsed)),
+            // discord keeps a relative timestamp counting up by itself
+            (Some(started), None) => write!(f, "⏱️ started <t:{}:R>", started / 1000),
+            (None, None) => write!(f, "⏱️ starts on your first guess"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Speedrun {
+    guild: GuildId,
+    user: UserId,
+    length: usize,
+    millis: i64,
+    guesses: usize,
+    hard_mode: bool,
+    finished: UtcDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FastestSolve {
+    #[serde(rename = "_id")]
+    pub user: UserId,
+    pub millis: i64,
+    pub guesses: usize,
+}
+
+/// Solved speedruns, kept for each server's leaderboard.
+#[derive(Debug, Clone)]
+pub struct Speedruns {
+    collection: Collection<Speedrun>,
+}
+
+impl Speedruns {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("wordle_speedruns"),
+        }
+    }
+
+    pub async fn save(
+        &self,
+        guild: GuildId,
+        user: UserId,
+        length: usize,
+        millis: i64,
+        guesses: usize,
+        hard_mode: bool,
+    ) -> DbResult<()> {
+        self.collection
+            .insert_one(
+                Speedrun {
+                    guild,
+                    user,
+                    length,
+                    millis,
+                    guesses,
+                    hard_mode,
+                    finished: Utc::now(),
+                },
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Each user's fastest solve in the server, fastest first.
+    /// Hard mode solves are ranked on their own.
+    pub async fn fastest(
+        &self,
+        guild: GuildId,
+        length: usize,
+        hard_mode: bool,
+        users: &[UserId],
+        limit: i64,
+    ) -> DbResult<Vec<FastestSolve>> {
+        let guild = mongodb::bson::ser::to_bson(&guild).expect("implements serialize");
+        let users = mongodb::bson::ser::to_bson(users).expect("implements serialize");
+
+        let pipeline = [
+            doc! { "$match": {
+                "guild": guild,
+                "length": length as i64,
+                "hard_mode": hard_mode,
+                "user": { "$in": users },
+            } },
+            doc! { "$sort": { "millis": 1, "finished": 1 } },
+            doc! { "$group": {
+                "_id": "$user",
+                "millis": { "$first": "$millis" },
+                "guesses": { "$first": "$guesses" },
+            } },
+            doc! { "$sort": { "millis": 1, "_id": 1 } },
+            doc! { "$limit": limit },
+        ];
+
+        let solves = self
+            .collection
+            .aggregate(pipeline, None)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(mongodb::bson::from_document::<FastestSolve>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(solves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_time, sent_at, Timer};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn snowflake_time() {
+        // from discord's documentation
+        assert_eq!(sent_at(175_928_847_299_117_063), 1_462_015_105_796);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_time(65_250), "1:05.250");
+        assert_eq!(format_time(999), "0:00.999");
+        assert_eq!(format_time(-5), "0:00.000");
+    }
+
+    #[test]
+    fn timer() {
+        let mut timer = Timer::default();
+        assert_eq!(timer.to_string(), "⏱️ starts on your first guess");
+
+        timer.record(10_000, false);
+        assert_eq!(timer.elapsed(), None);
+        assert_eq!(timer.to_string(), "⏱️ started <t:10:R>");
+
+        timer.record(25_500, true);
+        assert_eq!(timer.elapsed(), Some(15_500));
+        assert_eq!(timer.to_string(), "⏱️ 0:15.500");
+    }
+}
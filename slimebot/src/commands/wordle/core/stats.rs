@@ -0,0 +1,244 @@
+use super::GameRecord;
+
+/// Aggregated results of every daily wordle a user has finished.
+#[derive(Debug, Clone, Default)]
+pub struct UserStats {
+    pub played: u32,
+    pub solved: u32,
+
+    /// Solves of dailies played in hard mode.
+    pub hard_solved: u32,
+
+    /// Solves of dailies where hints were used.
+    pub assisted: u32,
+
+    /// The number of the most recent daily the user finished, solved or not.
+    last_played: Option<u32>,
+
+    /// Numbers of every solved daily, in ascending order.
+    solved_puzzles: Vec<u32>,
+
+    /// Guess counts of every solved daily, in the same order as `solved_puzzles`.
+    solved_guesses: Vec<u32>,
+}
+
+impl UserStats {
+    /// Counts a finished daily. Dailies have to be counted in order of their numbers.
+    pub fn count(&mut self, number: u32, game: &GameRecord) {
+        self.played += 1;
+        self.last_played = self.last_played.max(Some(number));
+
+        if game.is_solved() {
+            self.solved += 1;
+            self.hard_solved += u32::from(game.hard_mode);
+            self.assisted += u32::from(game.hints > 0);
+            self.solved_puzzles.push(number);
+            self.solved_guesses.push(game.num_guesses as u32);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.played == 0
+    }
+
+    pub fn win_percentage(&self) -> u32 {
+        if self.played == 0 {
+            0
+        } else {
+            self.solved * 100 / self.played
+        }
+    }
+
+    /// Counts how many dailies were solved in each number of guesses, starting at 1.
+    pub fn distribution(&self) -> Vec<u32> {
+        let max = self
+            .solved_guesses
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(6);
+        let mut distribution = vec![0; max as usize];
+
+        for guesses in &self.solved_guesses {
+            if let Some(count) = guesses
+                .checked_sub(1)
+                .and_then(|index| distribution.get_mut(index as usize))
+            {
+                *count += 1;
+            }
+        }
+
+        distribution
+    }
+
+    pub fn max_streak(&self) -> u32 {
+        longest_run(&self.solved_puzzles)
+    }
+
+    /// The current streak, given the number of the latest daily.
+    ///
+    /// A streak is only broken by a daily that was finished without being solved,
+    /// or one that expired without being finished; the latest daily can still be
+    /// played, so not having finished it yet doesn't break anything.
+    pub fn current_streak(&self, latest: u32) -> u32 {
+        let end = if self.solved_puzzles.last() == Some(&latest) {
+            latest
+        } else if self.last_played == Some(latest) {
+            return 0;
+        } else {
+            latest.saturating_sub(1)
+        };
+
+        run_ending_at(&self.solved_puzzles, end)
+    }
+}
+
+/// Length of the longest run of consecutive numbers in a sorted slice.
+pub(super) fn longest_run(numbers: &[u32]) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous = None;
+
+    for &number in numbers {
+        current = match previous {
+            Some(previous) if previous + 1 == number => current + 1,
+            _ => 1,
+        };
+
+        longest = longest.max(current);
+        previous = Some(number);
+    }
+
+    longest
+}
+
+/// Length of the run of consecutive numbers in a sorted slice that ends at `end`.
+fn run_ending_at(numbers: &[u32], end: u32) -> u32 {
+    if numbers.binary_search(&end).is_err() {
+        return 0;
+    }
+
+    numbers
+        .iter()
+        .rev()
+        .skip_while(|&&number| number > end)
+        .zip((0..=end).rev())
+        .take_while(|&(&number, expected)| number == expected)
+        .count() as u32
+}
+
+impl std::fmt::Display for UserStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let distribution = self.distribution();
+        let most = distribution.iter().copied().max().unwrap_or(0).max(1);
+
+        let rows = distribution
+            .iter()
+            .enumerate()
+            .map(|(index, &count)| {
+                format!(
+                    "`{guesses}` {bar} {count}",
+                    guesses = index + 1,
+                    bar = "🟩".repeat((count * 10 / most) as usize)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(f, "{rows}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poise::serenity_prelude::UserId;
+
+    use super::{GameRecord, UserStats};
+    use pretty_assertions::assert_eq;
+
+    fn stats(last_played: u32, solved_puzzles: &[u32]) -> UserStats {
+        UserStats {
+            played: last_played,
+            solved: solved_puzzles.len() as u32,
+            hard_solved: 0,
+            assisted: 0,
+            last_played: Some(last_played),
+            solved_guesses: vec![4; solved_puzzles.len()],
+            solved_puzzles: solved_puzzles.to_vec(),
+        }
+    }
+
+    #[test]
+    fn max_streak() {
+        assert_eq!(stats(10, &[1, 2, 3, 5, 6, 7, 8, 10]).max_streak(), 4);
+        assert_eq!(stats(10, &[]).max_streak(), 0);
+        assert_eq!(stats(10, &[10]).max_streak(), 1);
+    }
+
+    #[test]
+    fn current_streak_includes_latest() {
+        assert_eq!(stats(10, &[1, 2, 8, 9, 10]).current_streak(10), 3);
+    }
+
+    #[test]
+    fn current_streak_latest_unplayed() {
+        assert_eq!(stats(9, &[1, 2, 8, 9]).current_streak(10), 2);
+    }
+
+    #[test]
+    fn current_streak_latest_failed() {
+        assert_eq!(stats(10, &[1, 2, 8, 9]).current_streak(10), 0);
+    }
+
+    #[test]
+    fn current_streak_broken() {
+        assert_eq!(stats(8, &[1, 2, 7, 8]).current_streak(10), 0);
+    }
+
+    #[test]
+    fn distribution() {
+        let stats = UserStats {
+            played: 5,
+            solved: 4,
+            hard_solved: 1,
+            assisted: 0,
+            last_played: Some(5),
+            solved_puzzles: vec![1, 2, 3, 4],
+            solved_guesses: vec![3, 4, 4, 8],
+        };
+
+        assert_eq!(stats.distribution(), vec![0, 0, 1, 2, 0, 0, 0, 1]);
+        assert_eq!(stats.win_percentage(), 80);
+    }
+
+    #[test]
+    fn counts_games() {
+        let words = kwordle::classic::words_list();
+        let answer = kwordle::Word::from_str(&words, "onion").unwrap();
+
+        let game = |guesses: &[&str], hard_mode: bool, hints: usize| {
+            let mut played = kwordle::Guesses::new(None);
+
+            for guess in guesses {
+                played.push(answer.guess(kwordle::Word::from_str(&words, guess).unwrap()));
+            }
+
+            GameRecord::new(UserId::new(1), played, true, hard_mode).with_hints(hints)
+        };
+
+        let mut stats = UserStats::default();
+        stats.count(1, &game(&["chair", "onion"], false, 0));
+        stats.count(2, &game(&["chair", "point", "onion"], true, 2));
+        stats.count(3, &game(&["chair"], false, 1));
+        stats.count(4, &game(&["onion"], false, 1));
+
+        assert_eq!(stats.played, 4);
+        assert_eq!(stats.solved, 3);
+        assert_eq!(stats.hard_solved, 1);
+        // the unsolved game with a hint isn't a solve with hints
+        assert_eq!(stats.assisted, 2);
+        assert_eq!(stats.distribution(), vec![1, 1, 1, 0, 0, 0]);
+        assert_eq!(stats.current_streak(4), 1);
+    }
+}
@@ -1,96 +1,300 @@
-use rand::seq::IteratorRandom;
-use std::{collections::HashSet, fs};
+use std::{
+    collections::HashSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
 
-#[derive(Debug, Clone)]
-pub struct WordsList {
-    guesses: HashSet<String>,
-    answers: HashSet<String>,
+use tracing::{info, warn};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("couldn't read {path:?}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+
+    #[error("{path:?} doesn't contain any valid words")]
+    Empty { path: PathBuf },
+
+    #[error("invalid words list: {0:?}")]
+    Parse(kwordle::word::ParseWordError),
 }
 
-impl WordsList {
-    pub fn load(/*cfg: WordleConfig*/) -> Self {
-        let guesses = fs::read_to_string("./wordle/guesses.txt")
-            .unwrap_or_else(|_| {
-                fs::read_to_string("/wordle/guesses.txt")
-                    .expect("guesses should be at ./wordle/guesses.txt or /wordle/guesses.txt")
-            })
-            .lines()
-            .map(|s| s.to_owned())
-            .collect::<HashSet<String>>();
+/// Reads one word per line, ignoring blank lines and case.
+pub fn read_words(path: &Path) -> Result<Vec<String>, Error> {
+    let words = parse_words(&fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_owned(),
+        source,
+    })?);
 
-        assert!(!guesses.is_empty(), "guesses file should not be empty");
+    if words.is_empty() {
+        return Err(Error::Empty {
+            path: path.to_owned(),
+        });
+    }
 
-        let answers = fs::read_to_string("./wordle/answers.txt")
-            .unwrap_or_else(|_| {
-                fs::read_to_string("/wordle/answers.txt")
-                    .expect("answers should be at ./wordle/answers.txt or /wordle/answers.txt")
-            })
-            .lines()
-            .map(|s| s.to_owned())
-            .collect::<HashSet<String>>();
+    Ok(words)
+}
+
+fn parse_words(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum List {
+    Guesses,
+    Answers,
+}
 
-        Self { guesses, answers }
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Guesses => write!(f, "guesses"),
+            Self::Answers => write!(f, "answers"),
+        }
     }
+}
 
-    pub fn random_answer(&self) -> Word {
-        let word = self
-            .answers
-            .iter()
-            .choose(&mut rand::thread_rng())
-            .expect("file should not be empty");
+/// A problem with a words list, fixed while loading it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The word was listed more than once, and the repeats were dropped.
+    Duplicate { word: String, list: List },
+    /// The word doesn't have the right number of letters, and was dropped.
+    WrongLength { word: String, list: List },
+    /// The word has letters other than a to z, which can't be played, and was dropped.
+    UnsupportedLetter { word: String, list: List },
+    /// The answer wasn't in the guesses list, and was added to it.
+    AnswerNotGuess { word: String },
+}
 
-        Word::parse(word)
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate { word, list } => {
+                write!(f, "`{word}` is in the {list} list more than once")
+            }
+            Self::WrongLength { word, list } => {
+                write!(f, "`{word}` in the {list} list is the wrong length")
+            }
+            Self::UnsupportedLetter { word, list } => {
+                write!(
+                    f,
+                    "`{word}` in the {list} list has letters that can't be played"
+                )
+            }
+            Self::AnswerNotGuess { word } => {
+                write!(f, "answer `{word}` is missing from the guesses list")
+            }
+        }
     }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Validated {
+    pub guesses: Vec<String>,
+    pub answers: Vec<String>,
+    pub problems: Vec<Problem>,
+}
+
+/// Checks a guesses list and answers list for words of length `N`.
+///
+/// Instead of failing, every problem found is fixed and reported, so a typo
+/// in a long list doesn't stop the bot from starting.
+pub fn validate<const N: usize>(guesses: Vec<String>, answers: Vec<String>) -> Validated {
+    let mut problems = Vec::new();
+
+    let mut clean = |words: Vec<String>, list: List| {
+        let mut seen = HashSet::with_capacity(words.len());
+
+        words
+            .into_iter()
+            .filter(|word| {
+                if !word.chars().all(|c| c.is_ascii_lowercase()) {
+                    problems.push(Problem::UnsupportedLetter {
+                        word: word.clone(),
+                        list,
+                    });
+                    false
+                } else if word.len() != N {
+                    problems.push(Problem::WrongLength {
+                        word: word.clone(),
+                        list,
+                    });
+                    false
+                } else if !seen.insert(word.clone()) {
+                    problems.push(Problem::Duplicate {
+                        word: word.clone(),
+                        list,
+                    });
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut guesses = clean(guesses, List::Guesses);
+    let answers = clean(answers, List::Answers);
 
-    pub fn valid_guess(&self, guess: &str) -> bool {
-        let guess = &guess.to_lowercase();
+    let known: HashSet<&String> = guesses.iter().collect();
+    let missing: Vec<String> = answers
+        .iter()
+        .filter(|answer| !known.contains(answer))
+        .cloned()
+        .collect();
 
-        self.guesses.contains(guess) || self.answers.contains(guess)
+    for word in missing {
+        guesses.push(word.clone());
+        problems.push(Problem::AnswerNotGuess { word });
     }
 
-    pub fn get_word(&self, s: &str) -> Option<Word> {
-        self.valid_guess(s).then_some(Word::parse(s))
+    Validated {
+        guesses,
+        answers,
+        problems,
     }
 }
 
-impl AsRef<WordsList> for WordsList {
-    fn as_ref(&self) -> &WordsList {
-        self
+pub fn load<const N: usize>(
+    guesses_file: &Path,
+    answers_file: &Path,
+) -> Result<kwordle::WordsList<N>, Error> {
+    build(
+        read_words(guesses_file)?,
+        read_words(answers_file)?,
+        answers_file,
+    )
+}
+
+/// Validates words that were already read, and makes a words list out of them.
+/// `answers_file` is only for the error if no answers are left.
+pub fn build<const N: usize>(
+    guesses: Vec<String>,
+    answers: Vec<String>,
+    answers_file: &Path,
+) -> Result<kwordle::WordsList<N>, Error> {
+    let Validated {
+        guesses,
+        answers,
+        problems,
+    } = validate::<N>(guesses, answers);
+
+    for problem in &problems {
+        warn!(length = N, "{problem}");
     }
+
+    if answers.is_empty() {
+        return Err(Error::Empty {
+            path: answers_file.to_owned(),
+        });
+    }
+
+    info!(
+        length = N,
+        guesses = guesses.len(),
+        answers = answers.len(),
+        problems = problems.len(),
+        "loaded words list"
+    );
+
+    kwordle::WordsList::from_strs(&guesses, &answers).map_err(Error::Parse)
+}
+
+/// Reads a word that was saved before, like an old daily's answer,
+/// without checking that it's in a words list that might have changed since.
+pub fn saved_word<const N: usize>(word: &str) -> Option<kwordle::Word<N>> {
+    let words = vec![word.to_owned()];
+    let list = kwordle::WordsList::<N>::from_strs(&words, &words).ok()?;
+
+    kwordle::Word::from_str(&list, word).ok()
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use super::{parse_words, saved_word, validate, List, Problem};
+    use pretty_assertions::assert_eq;
 
-    use super::WordsList;
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|&word| word.to_owned()).collect()
+    }
 
     #[test]
-    fn fetch_answers() {
-        let words = ["amber", "mummy", "opals", "sonar", "today"];
+    fn parse() {
+        let text = "amber\n\n  Mummy\nopals \r\nSONAR\n";
 
-        let list = WordsList {
-            guesses: HashSet::new(),
-            answers: HashSet::from_iter(words.map(|s| s.to_owned())),
-        };
+        assert_eq!(parse_words(text), vec!["amber", "mummy", "opals", "sonar"]);
+    }
 
-        for _ in 0..10 {
-            let answer = list.random_answer();
-            assert!(words.contains(&answer.to_string().as_str()))
-        }
+    #[test]
+    fn valid() {
+        let validated = validate::<5>(
+            strings(&["amber", "mummy", "opals", "sonar"]),
+            strings(&["amber", "sonar"]),
+        );
+
+        assert_eq!(validated.problems, vec![]);
+        assert_eq!(
+            validated.guesses,
+            strings(&["amber", "mummy", "opals", "sonar"])
+        );
+        assert_eq!(validated.answers, strings(&["amber", "sonar"]));
     }
 
     #[test]
-    fn fetch_guesses() {
-        let words = ["amber", "mummy", "opals", "sonar", "today"];
+    fn problems() {
+        let validated = validate::<5>(
+            strings(&["amber", "mummy", "amber", "opal", "niños"]),
+            strings(&["amber", "today", "today", "ambers", "große"]),
+        );
 
-        let list = WordsList {
-            guesses: HashSet::new(),
-            answers: HashSet::from_iter(words.map(|s| s.to_owned())),
-        };
+        assert_eq!(
+            validated.problems,
+            vec![
+                Problem::Duplicate {
+                    word: "amber".to_owned(),
+                    list: List::Guesses
+                },
+                Problem::WrongLength {
+                    word: "opal".to_owned(),
+                    list: List::Guesses
+                },
+                Problem::UnsupportedLetter {
+                    word: "niños".to_owned(),
+                    list: List::Guesses
+                },
+                Problem::Duplicate {
+                    word: "today".to_owned(),
+                    list: List::Answers
+                },
+                Problem::WrongLength {
+                    word: "ambers".to_owned(),
+                    list: List::Answers
+                },
+                Problem::UnsupportedLetter {
+                    word: "große".to_owned(),
+                    list: List::Answers
+                },
+                Problem::AnswerNotGuess {
+                    word: "today".to_owned()
+                },
+            ]
+        );
 
-        for word in words {
-            assert!(list.valid_guess(word))
-        }
+        assert_eq!(validated.guesses, strings(&["amber", "mummy", "today"]));
+        assert_eq!(validated.answers, strings(&["amber", "today"]));
+    }
+
+    #[test]
+    fn saved() {
+        // not in the classic list
+        assert_eq!(
+            saved_word::<5>("xyzzy").map(|word| word.to_string()),
+            Some("xyzzy".to_owned())
+        );
+        assert!(saved_word::<5>("onions").is_none());
     }
 }
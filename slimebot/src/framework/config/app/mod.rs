@@ -1,10 +1,12 @@
 use std::{
     borrow::Cow,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use super::Environment;
-use poise::serenity_prelude::{ChannelId, RoleId};
+use chrono::{FixedOffset, NaiveTime};
+use poise::serenity_prelude::{ChannelId, GuildId, RoleId};
 use rand::seq::IteratorRandom;
 use serde::{Deserialize, Serialize};
 use tracing::{error, warn};
@@ -182,8 +184,97 @@ impl BugReportsConfig {
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(default)]
 pub struct WordleConfig {
-    //pub guesses_file: String,
-    //pub answers_file: String,
+    pub guesses_file: Option<PathBuf>,
+    pub answers_file: Option<PathBuf>,
     pub role_id: Option<RoleId>,
     pub channel_id: Option<ChannelId>,
+    pub extra_lists: Vec<WordsListFiles>,
+    pub languages: Vec<LanguageFiles>,
+    pub guilds: Vec<GuildWordleConfig>,
+    idle_timeout_minutes: Option<u64>,
+    pub daily_time: Option<NaiveTime>,
+    /// The timezone of `daily_time`, like `+02:00`.
+    #[serde(with = "utc_offset")]
+    pub utc_offset: Option<FixedOffset>,
+}
+
+impl WordleConfig {
+    /// How long a game can go without guesses before it's stopped.
+    /// Defaults to 30 minutes, and 0 means games never time out.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        match self.idle_timeout_minutes.unwrap_or(30) {
+            0 => None,
+            minutes => Some(Duration::from_secs(minutes * 60)),
+        }
+    }
+
+    /// The server's settings, if it has its own daily wordles.
+    pub fn guild(&self, guild: Option<GuildId>) -> Option<&GuildWordleConfig> {
+        let guild = guild?;
+        self.guilds.iter().find(|config| config.guild_id == guild)
+    }
+
+    /// Where a server's daily wordles are announced.
+    pub fn channel_in(&self, guild: Option<GuildId>) -> Option<ChannelId> {
+        self.guild(guild)
+            .map_or(self.channel_id, |config| config.channel_id)
+    }
+
+    /// Who gets pinged for a server's daily wordles.
+    pub fn role_in(&self, guild: Option<GuildId>) -> Option<RoleId> {
+        self.guild(guild)
+            .map_or(self.role_id, |config| config.role_id)
+    }
+}
+
+/// A server with its own daily wordles, instead of the ones every server shares.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GuildWordleConfig {
+    pub guild_id: GuildId,
+    pub channel_id: Option<ChannelId>,
+    pub role_id: Option<RoleId>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WordsListFiles {
+    pub length: usize,
+    pub guesses_file: PathBuf,
+    pub answers_file: PathBuf,
+}
+
+/// Where to find the words for a language other than english.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LanguageFiles {
+    /// Like `spanish`, `german` or `portuguese`.
+    pub language: String,
+    pub guesses_file: PathBuf,
+    pub answers_file: PathBuf,
+}
+
+/// Reads a utc offset from a string like `+02:00`, so a bad one stops the config from loading.
+mod utc_offset {
+    use chrono::FixedOffset;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_some(&offset.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|offset| {
+                offset.parse().map_err(|err| {
+                    D::Error::custom(format!("invalid wordle.utc_offset {offset:?}: {err}"))
+                })
+            })
+            .transpose()
+    }
 }
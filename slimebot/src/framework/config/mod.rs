@@ -2,6 +2,7 @@ mod app;
 use std::ops::Deref;
 
 pub use app::AppConfig as Config;
+pub use app::{LanguageFiles, WordleConfig};
 
 pub mod env;
 pub use env::Environment;
@@ -48,7 +48,7 @@ impl PoiseData {
 
         let started = Utc::now();
 
-        let wordle = WordleData::new(&db);
+        let wordle = WordleData::new(&db, &config.wordle);
 
         /* let (error_tx, error_rx) = ErrorHandler::channel();
         error_rx.spawn(); */
@@ -9,6 +9,7 @@ use thisslime::TracingError;
 use tracing::trace;
 
 use crate::{
+    commands::wordle::core::game::restore_interrupted,
     errors::{CommandError, SendMessageError},
     PoiseData,
 };
@@ -132,10 +133,17 @@ async fn event_handler(
             }
         }
 
-        FullEvent::Ready { .. } => data
-            .nortverse()
-            .clone()
-            .subscribe_task(serenity_ctx.cache.clone(), serenity_ctx.http.clone()),
+        FullEvent::Ready { .. } => {
+            data.nortverse()
+                .clone()
+                .subscribe_task(serenity_ctx.cache.clone(), serenity_ctx.http.clone());
+
+            data.wordle()
+                .clone()
+                .rollover_task(serenity_ctx.http.clone(), data.config().wordle.clone());
+
+            restore_interrupted(serenity_ctx, data).await?;
+        }
 
         _ => (),
     }